@@ -0,0 +1,51 @@
+//! Time DOT generation for a large chain of operations.
+//!
+//! Run with `cargo bench`, and compare against `cargo bench --features rayon`.
+use open_hypergraphs::lax::OpenHypergraph;
+use open_hypergraphs_dot::{generate_dot_with, Options};
+use std::time::Instant;
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum Obj {
+    Bit,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum Gate {
+    Not,
+    And,
+}
+
+/// A chain of `n` gates alternating between `Not` and `And`, each feeding the next
+fn chain(n: usize) -> OpenHypergraph<Obj, Gate> {
+    let mut graph = OpenHypergraph::empty();
+    let mut previous = None;
+    for i in 0..n {
+        let (op, arity) = if i % 2 == 0 {
+            (Gate::Not, 1)
+        } else {
+            (Gate::And, 2)
+        };
+        let (_, (sources, targets)) =
+            graph.new_operation(op, vec![Obj::Bit; arity], vec![Obj::Bit]);
+        match previous {
+            Some(output) => graph.unify(output, sources[0]),
+            None => graph.sources = sources.clone(),
+        }
+        previous = Some(targets[0]);
+    }
+    graph.targets = previous.into_iter().collect();
+    graph
+}
+
+fn main() {
+    let opts = Options::default();
+    for n in [1_000, 10_000, 100_000] {
+        let graph = chain(n);
+        let start = Instant::now();
+        let dot_graph = generate_dot_with(&graph, &opts);
+        let elapsed = start.elapsed();
+        std::hint::black_box(dot_graph);
+        println!("{:>7} operations: {:?}", n, elapsed);
+    }
+}
@@ -128,15 +128,7 @@ fn xor() -> Term {
     .unwrap()
 }
 
-use graphviz_rust::dot_structures::Graph;
-use graphviz_rust::printer::{DotPrinter, PrinterContext};
-use open_hypergraphs_dot::{generate_dot_with, Options, Orientation};
-
-/// Render a graph to DOT format string
-pub fn render_dot(graph: &Graph) -> String {
-    let mut ctx = PrinterContext::default();
-    graph.print(&mut ctx)
-}
+use open_hypergraphs_dot::{generate_dot_with, render_dot, Options, Orientation};
 
 pub fn edge_label(g: &Gate) -> String {
     use Gate::*;
@@ -158,7 +150,7 @@ fn render_adder(graph: &Term, file_slug: String) -> std::io::Result<()> {
     let opts = Options {
         orientation: Orientation::TB,
         // hide all the node labels by default, since they are all 'Bit'
-        node_label: Box::new(|_n| "".to_string()),
+        node_label: Box::new(|_n: &Bit| "".to_string()),
         edge_label: Box::new(edge_label),
         ..Default::default()
     };
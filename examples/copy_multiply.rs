@@ -1,14 +1,11 @@
 use open_hypergraphs::lax::OpenHypergraph;
-use open_hypergraphs_dot::generate_dot;
+use open_hypergraphs_dot::{generate_dot, render_dot};
 
 use std::fs::File;
 use std::hash::Hash;
 use std::io::Write;
 use std::process::Command;
 
-use graphviz_rust::dot_structures::Graph;
-use graphviz_rust::printer::{DotPrinter, PrinterContext};
-
 #[derive(PartialEq, Clone, Debug, Hash)]
 pub enum NodeType {
     A,
@@ -21,12 +18,6 @@ pub enum Operation {
     Mul,
 }
 
-/// Render a graph to DOT format string
-pub fn render_dot(graph: &Graph) -> String {
-    let mut ctx = PrinterContext::default();
-    graph.print(&mut ctx)
-}
-
 fn main() -> std::io::Result<()> {
     // Create a simple lax hypergraph: Copy operation connected to Multiply
     let mut graph = OpenHypergraph::<NodeType, Operation>::empty();
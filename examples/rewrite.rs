@@ -0,0 +1,86 @@
+use open_hypergraphs::lax::OpenHypergraph;
+use open_hypergraphs_dot::{generate_dot_many, render_dot, Options};
+
+use std::fs::File;
+use std::io::Write;
+use std::process::Command;
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum NodeType {
+    A,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum Operation {
+    Copy,
+    Mul,
+    Square,
+}
+
+// x ↦ x * x, by copying x and multiplying the copies
+fn copy_then_multiply() -> OpenHypergraph<NodeType, Operation> {
+    let mut graph = OpenHypergraph::empty();
+
+    let (_, (input_nodes, x)) = graph.new_operation(
+        Operation::Copy,
+        vec![NodeType::A],
+        vec![NodeType::A, NodeType::A],
+    );
+    let (_, (y, output_nodes)) = graph.new_operation(
+        Operation::Mul,
+        vec![NodeType::A, NodeType::A],
+        vec![NodeType::A],
+    );
+    graph.unify(x[0], y[0]);
+    graph.unify(x[1], y[1]);
+
+    graph.sources = input_nodes;
+    graph.targets = output_nodes;
+    graph
+}
+
+// The same term after rewriting it to a single Square operation
+fn square() -> OpenHypergraph<NodeType, Operation> {
+    let mut graph = OpenHypergraph::empty();
+
+    let (_, (input_nodes, output_nodes)) =
+        graph.new_operation(Operation::Square, vec![NodeType::A], vec![NodeType::A]);
+
+    graph.sources = input_nodes;
+    graph.targets = output_nodes;
+    graph
+}
+
+fn main() -> std::io::Result<()> {
+    let before = copy_then_multiply();
+    let after = square();
+
+    // Draw both terms side by side in one graph, each in its own cluster
+    let dot_graph = generate_dot_many(
+        &[("before", &before), ("after", &after)],
+        &Options::default(),
+    );
+    let dot_string = render_dot(&dot_graph);
+
+    // Print DOT string
+    println!("Generated DOT representation:");
+    println!("{}", dot_string);
+
+    // Save DOT to file
+    let output_path = "examples/rewrite.dot";
+    let mut file = File::create(output_path)?;
+    file.write_all(dot_string.as_bytes())?;
+    println!("DOT file saved to {}", output_path);
+
+    // Try to render with GraphViz if available
+    let output_png = "examples/rewrite.png";
+    match Command::new("dot")
+        .args(["-Tpng", output_path, "-o", output_png])
+        .status()
+    {
+        Ok(status) if status.success() => println!("PNG image rendered to {}", output_png),
+        _ => println!("Note: Install GraphViz to render the DOT file as an image."),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,377 @@
+//! Structural analyses of hypergraphs used by the rendering options
+use crate::GenerateError;
+use open_hypergraphs::lax::{Hyperedge, NodeId, OpenHypergraph};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+/// Union-find lookup with path halving
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Union-find merge, keeping the smallest index as representative so results are deterministic
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let a = find(parent, a);
+    let b = find(parent, b);
+    if a < b {
+        parent[b] = a;
+    } else {
+        parent[a] = b;
+    }
+}
+
+/// Check that every node index referenced by the graph is in range, reporting the first which
+/// isn't
+pub(crate) fn check_node_indices<O, A>(graph: &OpenHypergraph<O, A>) -> Result<(), GenerateError> {
+    let num_nodes = graph.hypergraph.nodes.len();
+    let missing = |node: &NodeId| node.0 >= num_nodes;
+
+    let num_edges = graph.hypergraph.edges.len();
+    if graph.hypergraph.adjacency.len() != num_edges {
+        return Err(GenerateError::AdjacencyLength {
+            edges: num_edges,
+            adjacency: graph.hypergraph.adjacency.len(),
+        });
+    }
+
+    for (edge, hyperedge) in graph.hypergraph.adjacency.iter().enumerate() {
+        if let Some((port, node)) = hyperedge
+            .sources
+            .iter()
+            .enumerate()
+            .find(|(_, n)| missing(n))
+        {
+            return Err(GenerateError::EdgeSource {
+                edge,
+                port,
+                node: node.0,
+            });
+        }
+        if let Some((port, node)) = hyperedge
+            .targets
+            .iter()
+            .enumerate()
+            .find(|(_, n)| missing(n))
+        {
+            return Err(GenerateError::EdgeTarget {
+                edge,
+                port,
+                node: node.0,
+            });
+        }
+    }
+
+    if let Some((port, node)) = graph.sources.iter().enumerate().find(|(_, n)| missing(n)) {
+        return Err(GenerateError::InterfaceSource { port, node: node.0 });
+    }
+    if let Some((port, node)) = graph.targets.iter().enumerate().find(|(_, n)| missing(n)) {
+        return Err(GenerateError::InterfaceTarget { port, node: node.0 });
+    }
+
+    let (lefts, rights) = &graph.hypergraph.quotient;
+    for (index, (left, right)) in lefts.iter().zip(rights.iter()).enumerate() {
+        if let Some(node) = [left, right].into_iter().find(|n| missing(n)) {
+            return Err(GenerateError::Quotient {
+                index,
+                node: node.0,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Map each node to a representative of its connected component under the quotient relation
+pub(crate) fn quotient_representatives<O, A>(graph: &OpenHypergraph<O, A>) -> Vec<usize> {
+    let mut parent: Vec<usize> = (0..graph.hypergraph.nodes.len()).collect();
+
+    let (lefts, rights) = &graph.hypergraph.quotient;
+    for (left, right) in lefts.iter().zip(rights.iter()) {
+        // Dangling indices are drawn as phantom nodes of their own, so they merge with nothing
+        if left.0 < parent.len() && right.0 < parent.len() {
+            union(&mut parent, left.0, right.0);
+        }
+    }
+
+    (0..parent.len()).map(|i| find(&mut parent, i)).collect()
+}
+
+/// Find source connections `(edge, port)` which point backwards against the flow.
+///
+/// Operations are ordered by reverse DFS postorder, which is a topological order when the graph
+/// is acyclic. A connection into an operation is backwards when its wire is produced by an
+/// operation which comes no earlier in that order, i.e. it closes a feedback loop.
+pub(crate) fn back_connections<O, A>(graph: &OpenHypergraph<O, A>) -> HashSet<(usize, usize)> {
+    let adjacency = &graph.hypergraph.adjacency;
+
+    // Dangling node indices represent themselves, like the phantom nodes drawn for them
+    let mut rep = quotient_representatives(graph);
+    let referenced = adjacency
+        .iter()
+        .flat_map(|hyperedge| hyperedge.sources.iter().chain(&hyperedge.targets))
+        .map(|node| node.0 + 1)
+        .max()
+        .unwrap_or(0);
+    rep.extend(rep.len()..referenced);
+
+    // Operations producing and consuming each wire (indexed by representative)
+    let mut producers = vec![Vec::new(); rep.len()];
+    let mut consumers = vec![Vec::new(); rep.len()];
+    for (i, hyperedge) in adjacency.iter().enumerate() {
+        for node in &hyperedge.targets {
+            producers[rep[node.0]].push(i);
+        }
+        for node in &hyperedge.sources {
+            consumers[rep[node.0]].push(i);
+        }
+    }
+
+    // Iterative DFS over operations, recording postorder
+    let mut visited = vec![false; adjacency.len()];
+    let mut postorder = Vec::with_capacity(adjacency.len());
+    for root in 0..adjacency.len() {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut stack = vec![(root, successors(root, graph, &rep, &consumers))];
+        while let Some((op, next)) = stack.last_mut() {
+            match next.pop() {
+                Some(succ) if !visited[succ] => {
+                    visited[succ] = true;
+                    let succs = successors(succ, graph, &rep, &consumers);
+                    stack.push((succ, succs));
+                }
+                Some(_) => {}
+                None => {
+                    postorder.push(*op);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    let mut position = vec![0; adjacency.len()];
+    for (k, &op) in postorder.iter().rev().enumerate() {
+        position[op] = k;
+    }
+
+    let mut back = HashSet::new();
+    for (i, hyperedge) in adjacency.iter().enumerate() {
+        for (j, node) in hyperedge.sources.iter().enumerate() {
+            if producers[rep[node.0]]
+                .iter()
+                .any(|&p| position[p] >= position[i])
+            {
+                back.insert((i, j));
+            }
+        }
+    }
+
+    back
+}
+
+/// Operations consuming any target of operation `op`, in reverse order for DFS popping
+fn successors<O, A>(
+    op: usize,
+    graph: &OpenHypergraph<O, A>,
+    rep: &[usize],
+    consumers: &[Vec<usize>],
+) -> Vec<usize> {
+    let mut succs: Vec<usize> = graph.hypergraph.adjacency[op]
+        .targets
+        .iter()
+        .flat_map(|node| consumers[rep[node.0]].iter().copied())
+        .collect();
+    succs.reverse();
+    succs
+}
+
+/// A node or operation on a path through a hypergraph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PathStep {
+    Node(usize),
+    Edge(usize),
+}
+
+/// Find a shortest path from node `from` to node `to`, following data flow from each source of an
+/// operation to its targets, and quotient pairs in either direction. Returns the alternating
+/// sequence of nodes and operations, or `None` if either index is out of range or `to` is
+/// unreachable. Out-of-range references in the graph are never followed.
+pub(crate) fn shortest_path<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    from: usize,
+    to: usize,
+) -> Option<Vec<PathStep>> {
+    let num_nodes = graph.hypergraph.nodes.len();
+    if from >= num_nodes || to >= num_nodes {
+        return None;
+    }
+
+    // Operations consuming each node, and nodes unified with each node
+    let mut consumers = vec![Vec::new(); num_nodes];
+    for (i, hyperedge) in graph.hypergraph.adjacency.iter().enumerate() {
+        for node in hyperedge.sources.iter().filter(|node| node.0 < num_nodes) {
+            consumers[node.0].push(i);
+        }
+    }
+    let mut unified = vec![Vec::new(); num_nodes];
+    let (lefts, rights) = &graph.hypergraph.quotient;
+    for (left, right) in lefts.iter().zip(rights.iter()) {
+        if left.0 < num_nodes && right.0 < num_nodes {
+            unified[left.0].push(right.0);
+            unified[right.0].push(left.0);
+        }
+    }
+
+    // Breadth-first search, recording the predecessor node and operation (if any) of each node
+    let mut previous: Vec<Option<(usize, Option<usize>)>> = vec![None; num_nodes];
+    let mut visited = vec![false; num_nodes];
+    visited[from] = true;
+    let mut queue = VecDeque::from([from]);
+    while let Some(node) = queue.pop_front() {
+        if node == to {
+            break;
+        }
+
+        let via_edges = consumers[node].iter().flat_map(|&i| {
+            graph.hypergraph.adjacency[i]
+                .targets
+                .iter()
+                .filter(|t| t.0 < num_nodes)
+                .map(move |t| (t.0, Some(i)))
+        });
+        let via_quotient = unified[node].iter().map(|&n| (n, None));
+        for (next, edge) in via_edges.chain(via_quotient) {
+            if !visited[next] {
+                visited[next] = true;
+                previous[next] = Some((node, edge));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    if !visited[to] {
+        return None;
+    }
+
+    // Walk back from the end of the path
+    let mut path = vec![PathStep::Node(to)];
+    let mut node = to;
+    while let Some((prev, edge)) = previous[node] {
+        if let Some(i) = edge {
+            path.push(PathStep::Edge(i));
+        }
+        path.push(PathStep::Node(prev));
+        node = prev;
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Split a graph into its connected components, where nodes are linked by operations and by
+/// quotient pairs. Components are ordered by their lowest node (or, for operations without any
+/// nodes, edge) index, and each is reindexed with nodes and edges kept in their original order.
+/// Each global interface node goes to the interface of its component, keeping relative order.
+///
+/// A dangling node index gets a phantom slot of its own, joining the component of whatever
+/// references it, and stays dangling there: it is reindexed past the component's nodes, so it is
+/// drawn as a phantom node like in the whole graph.
+pub(crate) fn connected_components<O: Clone, A: Clone>(
+    graph: &OpenHypergraph<O, A>,
+) -> Vec<OpenHypergraph<O, A>> {
+    let num_nodes = graph.hypergraph.nodes.len();
+    let num_edges = graph.hypergraph.edges.len();
+    let (lefts, rights) = &graph.hypergraph.quotient;
+
+    // Out-of-range node indices referenced anywhere, in increasing order
+    let dangling: Vec<usize> = graph
+        .hypergraph
+        .adjacency
+        .iter()
+        .flat_map(|hyperedge| hyperedge.sources.iter().chain(&hyperedge.targets))
+        .chain(lefts.iter().chain(rights))
+        .chain(graph.sources.iter().chain(&graph.targets))
+        .map(|node| node.0)
+        .filter(|&n| n >= num_nodes)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    // Elements 0..num_nodes are nodes, then come edges, then the phantom slots of dangling nodes
+    let phantoms = num_nodes + num_edges;
+    let slot = |node: &NodeId| match dangling.binary_search(&node.0) {
+        Ok(k) => phantoms + k,
+        Err(_) => node.0,
+    };
+    let mut parent: Vec<usize> = (0..phantoms + dangling.len()).collect();
+    for (i, hyperedge) in graph.hypergraph.adjacency.iter().enumerate() {
+        for node in hyperedge.sources.iter().chain(hyperedge.targets.iter()) {
+            union(&mut parent, num_nodes + i, slot(node));
+        }
+    }
+    for (left, right) in lefts.iter().zip(rights.iter()) {
+        union(&mut parent, slot(left), slot(right));
+    }
+
+    // Number components in order of their representative
+    let mut component = vec![usize::MAX; parent.len()];
+    let mut count = 0;
+    for i in 0..parent.len() {
+        let root = find(&mut parent, i);
+        if component[root] == usize::MAX {
+            component[root] = count;
+            count += 1;
+        }
+        component[i] = component[root];
+    }
+
+    // Local index of each node and edge within its component, with phantoms after the nodes
+    let mut components: Vec<OpenHypergraph<O, A>> =
+        (0..count).map(|_| OpenHypergraph::empty()).collect();
+    let mut local = vec![0; parent.len()];
+    for i in 0..num_nodes {
+        let h = &mut components[component[i]].hypergraph;
+        local[i] = h.nodes.len();
+        h.nodes.push(graph.hypergraph.nodes[i].clone());
+    }
+    let mut num_phantoms = vec![0; count];
+    for k in phantoms..parent.len() {
+        let c = component[k];
+        local[k] = components[c].hypergraph.nodes.len() + num_phantoms[c];
+        num_phantoms[c] += 1;
+    }
+
+    let remap = |nodes: &[NodeId]| nodes.iter().map(|n| NodeId(local[slot(n)])).collect();
+    for i in 0..num_edges {
+        let hyperedge = &graph.hypergraph.adjacency[i];
+        let h = &mut components[component[num_nodes + i]].hypergraph;
+        h.edges.push(graph.hypergraph.edges[i].clone());
+        h.adjacency.push(Hyperedge {
+            sources: remap(&hyperedge.sources),
+            targets: remap(&hyperedge.targets),
+        });
+    }
+
+    for (left, right) in lefts.iter().zip(rights.iter()) {
+        let h = &mut components[component[slot(left)]].hypergraph;
+        h.quotient.0.push(NodeId(local[slot(left)]));
+        h.quotient.1.push(NodeId(local[slot(right)]));
+    }
+
+    for node in &graph.sources {
+        components[component[slot(node)]]
+            .sources
+            .push(NodeId(local[slot(node)]));
+    }
+    for node in &graph.targets {
+        components[component[slot(node)]]
+            .targets
+            .push(NodeId(local[slot(node)]));
+    }
+
+    components
+}
@@ -0,0 +1,313 @@
+/// Categorical palette used for automatic coloring (Tableau 10)
+pub const PALETTE: [&str; 10] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7",
+    "#9c755f", "#bab0ac",
+];
+
+/// Pick a palette color for a category name.
+///
+/// The choice depends only on `name` and `seed`, so a category gets the same color across runs
+/// and across graphs. Change `seed` to shift the assignment if two categories clash.
+pub fn color_for_category(name: &str, seed: u64) -> String {
+    let index = fnv1a(name.as_bytes(), seed) % PALETTE.len() as u64;
+    PALETTE[index as usize].to_string()
+}
+
+/// 64-bit FNV-1a hash, with the seed mixed into the offset basis.
+/// Unlike `DefaultHasher`, this is stable across runs and Rust versions.
+fn fnv1a(bytes: &[u8], seed: u64) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let mut hash = OFFSET_BASIS ^ seed.wrapping_mul(PRIME);
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+/// Color names of GraphViz's default X11 scheme, without numbered variants such as `red3`
+const X11_NAMES: &[&str] = &[
+    "aliceblue",
+    "antiquewhite",
+    "aqua",
+    "aquamarine",
+    "azure",
+    "beige",
+    "bisque",
+    "black",
+    "blanchedalmond",
+    "blue",
+    "blueviolet",
+    "brown",
+    "burlywood",
+    "cadetblue",
+    "chartreuse",
+    "chocolate",
+    "coral",
+    "cornflowerblue",
+    "cornsilk",
+    "crimson",
+    "cyan",
+    "darkblue",
+    "darkcyan",
+    "darkgoldenrod",
+    "darkgray",
+    "darkgreen",
+    "darkgrey",
+    "darkkhaki",
+    "darkmagenta",
+    "darkolivegreen",
+    "darkorange",
+    "darkorchid",
+    "darkred",
+    "darksalmon",
+    "darkseagreen",
+    "darkslateblue",
+    "darkslategray",
+    "darkslategrey",
+    "darkturquoise",
+    "darkviolet",
+    "deeppink",
+    "deepskyblue",
+    "dimgray",
+    "dimgrey",
+    "dodgerblue",
+    "firebrick",
+    "floralwhite",
+    "forestgreen",
+    "fuchsia",
+    "gainsboro",
+    "ghostwhite",
+    "gold",
+    "goldenrod",
+    "gray",
+    "green",
+    "greenyellow",
+    "grey",
+    "honeydew",
+    "hotpink",
+    "indianred",
+    "indigo",
+    "ivory",
+    "khaki",
+    "lavender",
+    "lavenderblush",
+    "lawngreen",
+    "lemonchiffon",
+    "lightblue",
+    "lightcoral",
+    "lightcyan",
+    "lightgoldenrod",
+    "lightgoldenrodyellow",
+    "lightgray",
+    "lightgreen",
+    "lightgrey",
+    "lightpink",
+    "lightsalmon",
+    "lightseagreen",
+    "lightskyblue",
+    "lightslateblue",
+    "lightslategray",
+    "lightslategrey",
+    "lightsteelblue",
+    "lightyellow",
+    "lime",
+    "limegreen",
+    "linen",
+    "magenta",
+    "maroon",
+    "mediumaquamarine",
+    "mediumblue",
+    "mediumorchid",
+    "mediumpurple",
+    "mediumseagreen",
+    "mediumslateblue",
+    "mediumspringgreen",
+    "mediumturquoise",
+    "mediumvioletred",
+    "midnightblue",
+    "mintcream",
+    "mistyrose",
+    "moccasin",
+    "navajowhite",
+    "navy",
+    "navyblue",
+    "none",
+    "oldlace",
+    "olive",
+    "olivedrab",
+    "orange",
+    "orangered",
+    "orchid",
+    "palegoldenrod",
+    "palegreen",
+    "paleturquoise",
+    "palevioletred",
+    "papayawhip",
+    "peachpuff",
+    "peru",
+    "pink",
+    "plum",
+    "powderblue",
+    "purple",
+    "rebeccapurple",
+    "red",
+    "rosybrown",
+    "royalblue",
+    "saddlebrown",
+    "salmon",
+    "sandybrown",
+    "seagreen",
+    "seashell",
+    "sienna",
+    "silver",
+    "skyblue",
+    "slateblue",
+    "slategray",
+    "slategrey",
+    "snow",
+    "springgreen",
+    "steelblue",
+    "tan",
+    "teal",
+    "thistle",
+    "tomato",
+    "transparent",
+    "turquoise",
+    "violet",
+    "violetred",
+    "webgray",
+    "webgreen",
+    "webgrey",
+    "webmaroon",
+    "webpurple",
+    "wheat",
+    "white",
+    "whitesmoke",
+    "x11gray",
+    "x11green",
+    "x11grey",
+    "x11maroon",
+    "x11purple",
+    "yellow",
+    "yellowgreen",
+];
+
+/// X11 color names which also have numbered variants `name1`..`name4`
+const X11_NUMBERED_NAMES: &[&str] = &[
+    "antiquewhite",
+    "aquamarine",
+    "azure",
+    "bisque",
+    "blue",
+    "brown",
+    "burlywood",
+    "cadetblue",
+    "chartreuse",
+    "chocolate",
+    "coral",
+    "cornsilk",
+    "cyan",
+    "darkgoldenrod",
+    "darkolivegreen",
+    "darkorange",
+    "darkorchid",
+    "darkseagreen",
+    "darkslategray",
+    "deeppink",
+    "deepskyblue",
+    "dodgerblue",
+    "firebrick",
+    "gold",
+    "goldenrod",
+    "green",
+    "honeydew",
+    "hotpink",
+    "indianred",
+    "ivory",
+    "khaki",
+    "lavenderblush",
+    "lemonchiffon",
+    "lightblue",
+    "lightcyan",
+    "lightgoldenrod",
+    "lightpink",
+    "lightsalmon",
+    "lightskyblue",
+    "lightsteelblue",
+    "lightyellow",
+    "magenta",
+    "maroon",
+    "mediumorchid",
+    "mediumpurple",
+    "mistyrose",
+    "navajowhite",
+    "olivedrab",
+    "orange",
+    "orangered",
+    "orchid",
+    "palegreen",
+    "paleturquoise",
+    "palevioletred",
+    "peachpuff",
+    "pink",
+    "plum",
+    "purple",
+    "red",
+    "rosybrown",
+    "royalblue",
+    "salmon",
+    "seagreen",
+    "seashell",
+    "sienna",
+    "skyblue",
+    "slateblue",
+    "slategray",
+    "snow",
+    "springgreen",
+    "steelblue",
+    "tan",
+    "thistle",
+    "tomato",
+    "turquoise",
+    "violetred",
+    "wheat",
+    "yellow",
+];
+
+/// Check that `color` is a GraphViz color, returning it in a form GraphViz accepts.
+///
+/// Accepts X11 color names (case-insensitively, with numbered variants `name1`..`name4` for the
+/// names which have them, or `gray0`..`gray100`), and hex strings `#rrggbb` and `#rrggbbaa`.
+/// GraphViz doesn't understand the short form `#rgb`, so it is accepted and expanded to `#rrggbb`.
+pub(crate) fn normalize_color(color: &str) -> Option<String> {
+    if let Some(hex) = color.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        return match hex.len() {
+            3 => Some(hex.chars().fold(String::from("#"), |mut s, c| {
+                s.push(c);
+                s.push(c);
+                s
+            })),
+            6 | 8 => Some(color.to_string()),
+            _ => None,
+        };
+    }
+
+    let lower = color.to_ascii_lowercase();
+    let name = lower.trim_end_matches(|c: char| c.is_ascii_digit());
+    let valid = match &lower[name.len()..] {
+        "" => X11_NAMES.contains(&name),
+        digits if matches!(name, "gray" | "grey") => {
+            digits.parse::<u32>().is_ok_and(|n| n <= 100)
+                && (digits == "0" || !digits.starts_with('0'))
+        }
+        "1" | "2" | "3" | "4" => X11_NUMBERED_NAMES.contains(&name),
+        _ => false,
+    };
+    valid.then(|| color.to_string())
+}
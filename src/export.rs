@@ -0,0 +1,207 @@
+#[cfg(feature = "exec")]
+use crate::render_dot;
+use crate::{generate_dot_with, MaybeSync, Options};
+use dot_structures::Graph;
+#[cfg(feature = "exec")]
+use dot_structures::{Attribute, Id, Stmt};
+use graphviz_rust::printer::{DotPrinter, PrinterContext};
+use open_hypergraphs::lax::OpenHypergraph;
+#[cfg(feature = "exec")]
+use std::ffi::OsStr;
+#[cfg(feature = "exec")]
+use std::fmt;
+use std::fmt::Debug;
+#[cfg(feature = "exec")]
+use std::io::Write;
+#[cfg(feature = "exec")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "exec")]
+use std::process::{Command, Stdio};
+
+/// Canvas size applied to every exported frame (inches, `!` forces scaling up to fit)
+#[cfg(feature = "exec")]
+const FRAME_SIZE: &str = "\"8,8!\"";
+
+/// Aspect ratio applied to every exported frame, so frames share exact dimensions
+#[cfg(feature = "exec")]
+const FRAME_RATIO: &str = "fill";
+
+////////////////////////////////////////////////////////////////////////////////
+// Errors
+
+/// Errors produced when rendering DOT output with the GraphViz `dot` binary
+#[cfg(feature = "exec")]
+#[derive(Debug)]
+pub enum DotError {
+    /// Failed to run `dot` or to write its output
+    Io(std::io::Error),
+    /// `dot` ran but exited unsuccessfully; contains its stderr
+    Dot(String),
+}
+
+#[cfg(feature = "exec")]
+impl fmt::Display for DotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DotError::Io(e) => write!(f, "io error: {}", e),
+            DotError::Dot(msg) => write!(f, "dot failed: {}", msg),
+        }
+    }
+}
+
+#[cfg(feature = "exec")]
+impl std::error::Error for DotError {}
+
+#[cfg(feature = "exec")]
+impl From<std::io::Error> for DotError {
+    fn from(e: std::io::Error) -> Self {
+        DotError::Io(e)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Printing
+
+/// Print a generated graph as DOT source. With [`Options::minify`] set, statements are separated
+/// by semicolons instead of newlines and indentation is dropped.
+pub fn print_dot<O, A>(graph: &Graph, opts: &Options<O, A>) -> String {
+    let mut ctx = PrinterContext::default();
+    if opts.minify {
+        ctx.always_inline().with_semi();
+    }
+    graph.print(&mut ctx)
+}
+
+/// Generate a lax open hypergraph and print it with [`print_dot`]. With
+/// [`Options::comment_header`] set, the output starts with a comment such as
+/// `// open-hypergraphs-dot v0.2.1: 12 nodes, 5 edges`, which GraphViz ignores.
+pub fn print_dot_with<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> String
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    let dot_string = print_dot(&generate_dot_with(graph, opts), opts);
+    if !opts.comment_header {
+        return dot_string;
+    }
+    format!(
+        "// {} v{}: {} nodes, {} edges\n{}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        graph.hypergraph.nodes.len(),
+        graph.hypergraph.edges.len(),
+        dot_string
+    )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Rendering
+
+/// Output formats supported by [`render_to_format`]
+#[cfg(feature = "exec")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Svg,
+    Png,
+    Pdf,
+}
+
+#[cfg(feature = "exec")]
+impl OutputFormat {
+    /// The name of the format as passed to `dot -T`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Svg => "svg",
+            OutputFormat::Png => "png",
+            OutputFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// Render a graph with the GraphViz `dot` binary, returning the bytes it writes to stdout.
+///
+/// Fails with [`std::io::ErrorKind::NotFound`] if `dot` is not on the `PATH`, and with an error
+/// containing its stderr if it exits unsuccessfully.
+#[cfg(feature = "exec")]
+pub fn render_to_format(graph: &Graph, format: OutputFormat) -> std::io::Result<Vec<u8>> {
+    let format = format!("-T{}", format.as_str());
+    run_dot(&[OsStr::new(&format)], render_dot(graph)).map_err(|e| match e {
+        DotError::Io(e) => e,
+        DotError::Dot(stderr) => std::io::Error::other(format!("dot failed: {}", stderr)),
+    })
+}
+
+/// Run `dot` with `args`, piping `dot_string` into it, and return what it writes to stdout.
+///
+/// A missing binary is reported as [`std::io::ErrorKind::NotFound`] with a message saying so.
+#[cfg(feature = "exec")]
+fn run_dot(args: &[&OsStr], dot_string: String) -> Result<Vec<u8>, DotError> {
+    let mut child = Command::new("dot")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => std::io::Error::new(
+                e.kind(),
+                "the GraphViz `dot` binary was not found on the PATH",
+            ),
+            _ => e,
+        })?;
+
+    // Write from a separate thread so a large output can't fill the pipe and deadlock
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let writer = std::thread::spawn(move || stdin.write_all(dot_string.as_bytes()));
+
+    let output = child.wait_with_output()?;
+    writer.join().expect("stdin writer panicked")?;
+    if !output.status.success() {
+        return Err(DotError::Dot(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+
+    Ok(output.stdout)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Frame export
+
+/// Render a sequence of graphs to numbered PNG frames `frame_000.png`, `frame_001.png`, ... in
+/// `dir`, using a fixed canvas size so the frames can be assembled into an animation.
+#[cfg(feature = "exec")]
+pub fn export_frames<O, A>(
+    graphs: &[OpenHypergraph<O, A>],
+    opts: &Options<O, A>,
+    dir: impl AsRef<Path>,
+) -> Result<Vec<PathBuf>, DotError>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+
+    let mut paths = Vec::with_capacity(graphs.len());
+    for (i, graph) in graphs.iter().enumerate() {
+        let mut dot_graph = generate_dot_with(graph, opts);
+
+        // Fix the canvas so frames don't jitter in dimensions
+        dot_graph.add_stmt(Stmt::Attribute(Attribute(
+            Id::Plain(String::from("size")),
+            Id::Plain(String::from(FRAME_SIZE)),
+        )));
+        dot_graph.add_stmt(Stmt::Attribute(Attribute(
+            Id::Plain(String::from("ratio")),
+            Id::Plain(String::from(FRAME_RATIO)),
+        )));
+
+        let path = dir.join(format!("frame_{:03}.png", i));
+        let args = [OsStr::new("-Tpng"), OsStr::new("-o"), path.as_os_str()];
+        run_dot(&args, print_dot(&dot_graph, opts))?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
@@ -0,0 +1,58 @@
+//! JSON output, for JavaScript visualization libraries
+use crate::{edge_label_text, node_label_text, Options};
+use open_hypergraphs::lax::OpenHypergraph;
+use serde_json::json;
+
+/// Generates a JSON description of the structure of a lax open hypergraph, with labels computed
+/// by `opts` (only the labelling, placeholder and numbering options apply).
+///
+/// The schema is:
+///
+/// ```json
+/// {
+///   "nodes": [{ "id": 0, "label": "A" }, ...],
+///   "edges": [{ "id": 0, "label": "Copy", "sources": [0], "targets": [1, 2] }, ...],
+///   "sources": [0],
+///   "targets": [5],
+///   "quotient": [[1, 3], [2, 4]]
+/// }
+/// ```
+///
+/// Node and edge `id`s are their indices in the hypergraph. An edge's `sources` and `targets`, the
+/// interface `sources` and `targets`, and each quotient pair `[left, right]` are node ids, in order.
+pub fn generate_json<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> String {
+    let nodes: Vec<_> = (0..graph.hypergraph.nodes.len())
+        .map(|i| json!({ "id": i, "label": node_label_text(graph, opts, i) }))
+        .collect();
+
+    let edges: Vec<_> = graph
+        .hypergraph
+        .adjacency
+        .iter()
+        .enumerate()
+        .map(|(i, hyperedge)| {
+            json!({
+                "id": i,
+                "label": edge_label_text(graph, opts, i),
+                "sources": hyperedge.sources.iter().map(|n| n.0).collect::<Vec<_>>(),
+                "targets": hyperedge.targets.iter().map(|n| n.0).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    let (lefts, rights) = &graph.hypergraph.quotient;
+    let quotient: Vec<_> = lefts
+        .iter()
+        .zip(rights.iter())
+        .map(|(left, right)| [left.0, right.0])
+        .collect();
+
+    json!({
+        "nodes": nodes,
+        "edges": edges,
+        "sources": graph.sources.iter().map(|n| n.0).collect::<Vec<_>>(),
+        "targets": graph.targets.iter().map(|n| n.0).collect::<Vec<_>>(),
+        "quotient": quotient,
+    })
+    .to_string()
+}
@@ -1,292 +1,2475 @@
-use dot_structures::{Attribute, Edge, EdgeTy, Graph, Id, Node, NodeId, Port, Stmt, Vertex};
-use open_hypergraphs::lax::OpenHypergraph;
+use dot_structures::{
+    Attribute, Edge, EdgeTy, Graph, Id, Node, NodeId, Port, Stmt, Subgraph, Vertex,
+};
+use graphviz_rust::printer::{DotPrinter, PrinterContext};
+use open_hypergraphs::array::vec::VecKind;
+use open_hypergraphs::lax::var::{forget, HasVar};
+use open_hypergraphs::lax::{self, Hyperedge, OpenHypergraph};
+use open_hypergraphs::strict;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 use std::fmt::Debug;
 
 pub mod options;
 pub use options::*;
 
+pub mod export;
+pub use export::*;
+
+pub mod color;
+pub use color::*;
+
+pub mod mermaid;
+pub use mermaid::*;
+
+pub mod tikz;
+pub use tikz::*;
+
+#[cfg(feature = "serde_json")]
+pub mod json;
+#[cfg(feature = "serde_json")]
+pub use json::*;
+
+mod analysis;
+
 pub fn generate_dot<O, A>(graph: &OpenHypergraph<O, A>) -> Graph
 where
-    O: Clone + Debug + PartialEq,
-    A: Clone + Debug + PartialEq,
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
 {
     generate_dot_with(graph, &Options::default())
 }
 
-/// Generates a GraphViz DOT representation of a lax open hypergraph
+/// A generated DOT graph, convertible from a lax open hypergraph with default options.
+///
+/// Derefs to the inner [`Graph`], so it can be passed to [`render_dot`] and friends directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotGraph(pub Graph);
+
+impl<O, A> From<&OpenHypergraph<O, A>> for DotGraph
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    fn from(graph: &OpenHypergraph<O, A>) -> Self {
+        DotGraph(generate_dot(graph))
+    }
+}
+
+impl From<DotGraph> for Graph {
+    fn from(dot_graph: DotGraph) -> Self {
+        dot_graph.0
+    }
+}
+
+impl std::ops::Deref for DotGraph {
+    type Target = Graph;
+
+    fn deref(&self) -> &Graph {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for DotGraph {
+    fn deref_mut(&mut self) -> &mut Graph {
+        &mut self.0
+    }
+}
+
+/// Sizes of a lax open hypergraph, e.g. for choosing layout options before rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphSummary {
+    /// Number of nodes (wires)
+    pub nodes: usize,
+    /// Number of hyperedges (operations)
+    pub edges: usize,
+    /// Length of the source interface
+    pub sources: usize,
+    /// Length of the target interface
+    pub targets: usize,
+    /// Number of pairs of nodes unified by the quotient
+    pub quotient_pairs: usize,
+}
+
+/// Count the nodes, edges, interface lengths and quotient pairs of a lax open hypergraph
+pub fn graph_summary<O, A>(graph: &OpenHypergraph<O, A>) -> GraphSummary {
+    GraphSummary {
+        nodes: graph.hypergraph.nodes.len(),
+        edges: graph.hypergraph.edges.len(),
+        sources: graph.sources.len(),
+        targets: graph.targets.len(),
+        quotient_pairs: graph.hypergraph.quotient.0.len(),
+    }
+}
+
+/// Render a graph to a DOT format string
+///
+/// ```
+/// use open_hypergraphs::lax::OpenHypergraph;
+/// use open_hypergraphs_dot::{generate_dot, render_dot};
+///
+/// let mut graph = OpenHypergraph::<&str, &str>::empty();
+/// let (_, (sources, targets)) = graph.new_operation("Copy", vec!["A"], vec!["A", "A"]);
+/// graph.sources = sources;
+/// graph.targets = targets;
+///
+/// let dot_string = render_dot(&generate_dot(&graph));
+/// assert!(dot_string.starts_with("digraph G {"));
+/// assert!(dot_string.contains("e_0:t_0 -> n_1"));
+/// ```
+pub fn render_dot(graph: &Graph) -> String {
+    let mut ctx = PrinterContext::default();
+    graph.print(&mut ctx)
+}
+
+/// Write a graph in DOT format to `w`, one statement at a time, producing the same output as
+/// [`render_dot`] without building it as a single string
+pub fn write_dot<W: std::io::Write>(graph: &Graph, w: &mut W) -> std::io::Result<()> {
+    let (id, strict, stmts, directed) = match graph {
+        Graph::Graph { id, strict, stmts } => (id, *strict, stmts, false),
+        Graph::DiGraph { id, strict, stmts } => (id, *strict, stmts, true),
+    };
+
+    // Printing the graph without its statements gives the header, and leaves the context set up
+    // (indented, and directed or not) for printing the statements themselves
+    let mut ctx = PrinterContext::default();
+    let empty = if directed {
+        Graph::DiGraph {
+            id: id.clone(),
+            strict,
+            stmts: vec![],
+        }
+    } else {
+        Graph::Graph {
+            id: id.clone(),
+            strict,
+            stmts: vec![],
+        }
+    };
+    let shell = empty.print(&mut ctx);
+    let header = shell.strip_suffix("\n}").unwrap_or(&shell);
+    w.write_all(header.as_bytes())?;
+
+    for (k, stmt) in stmts.iter().enumerate() {
+        if k > 0 {
+            w.write_all(b"\n")?;
+        }
+        w.write_all(stmt.print(&mut ctx).as_bytes())?;
+    }
+
+    w.write_all(b"\n}")
+}
+
+/// A reference to a node which doesn't exist in a hand-built hypergraph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateError {
+    /// Source `port` of operation `edge` is the missing node `node`
+    EdgeSource {
+        edge: usize,
+        port: usize,
+        node: usize,
+    },
+    /// Target `port` of operation `edge` is the missing node `node`
+    EdgeTarget {
+        edge: usize,
+        port: usize,
+        node: usize,
+    },
+    /// Position `port` of the source interface is the missing node `node`
+    InterfaceSource { port: usize, node: usize },
+    /// Position `port` of the target interface is the missing node `node`
+    InterfaceTarget { port: usize, node: usize },
+    /// Quotient pair `index` refers to the missing node `node`
+    Quotient { index: usize, node: usize },
+    /// The number of operation labels and of adjacency entries differ
+    AdjacencyLength { edges: usize, adjacency: usize },
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::EdgeSource { edge, port, node } => {
+                write!(
+                    f,
+                    "source {} of operation {} is missing node {}",
+                    port, edge, node
+                )
+            }
+            GenerateError::EdgeTarget { edge, port, node } => {
+                write!(
+                    f,
+                    "target {} of operation {} is missing node {}",
+                    port, edge, node
+                )
+            }
+            GenerateError::InterfaceSource { port, node } => {
+                write!(f, "interface source {} is missing node {}", port, node)
+            }
+            GenerateError::InterfaceTarget { port, node } => {
+                write!(f, "interface target {} is missing node {}", port, node)
+            }
+            GenerateError::Quotient { index, node } => {
+                write!(f, "quotient pair {} refers to missing node {}", index, node)
+            }
+            GenerateError::AdjacencyLength { edges, adjacency } => write!(
+                f,
+                "{} operations but {} adjacency entries",
+                edges, adjacency
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+/// Like [`generate_dot`], but first checks that every node referenced by an operation, the
+/// interfaces or the quotient exists. [`generate_dot`] itself draws a phantom node for each
+/// missing one, and panics if the operation labels and adjacency differ in length.
+pub fn try_generate_dot<O, A>(graph: &OpenHypergraph<O, A>) -> Result<Graph, GenerateError>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    try_generate_dot_with(graph, &Options::default())
+}
+
+/// Like [`generate_dot_with`], but first checks that every node referenced by an operation, the
+/// interfaces or the quotient exists. [`generate_dot_with`] itself draws a phantom node for each
+/// missing one, and panics if the operation labels and adjacency differ in length.
+pub fn try_generate_dot_with<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+) -> Result<Graph, GenerateError>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    analysis::check_node_indices(graph)?;
+    Ok(generate_dot_with(graph, opts))
+}
+
+/// Generates a GraphViz DOT representation of a lax open hypergraph.
+///
+/// An empty hypergraph gives a valid graph holding only the graph, node and edge defaults (plus
+/// any frame or legend requested by `opts`); no interface or minimap is drawn for it.
 pub fn generate_dot_with<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> Graph
 where
-    O: Clone + Debug + PartialEq,
-    A: Clone + Debug + PartialEq,
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    new_graph(opts, generate_stmts(graph, opts))
+}
+
+/// Generates the statements of the DOT representation of a lax open hypergraph, in order, as
+/// wrapped into a graph by [`generate_dot_with`]. Use this to filter or rewrite statements
+/// before building the graph.
+pub fn generate_stmts<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> Vec<Stmt>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    generate_stmts_oriented(graph, opts, false)
+}
+
+/// Generates a GraphViz DOT representation of the transpose (dagger) of a lax open hypergraph:
+/// `graph.targets` become the input interface, `graph.sources` the output interface, and every
+/// connection is reversed. Operation records are drawn unchanged.
+pub fn generate_dot_transposed<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> Graph
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    new_graph(opts, generate_stmts_oriented(graph, opts, true))
+}
+
+/// Generate the statements of the DOT graph, optionally transposed
+fn generate_stmts_oriented<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+    transposed: bool,
+) -> Vec<Stmt>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
 {
     let theme = &opts.theme;
+    let mut stmts = generate_header_stmts(graph, opts);
+
+    // Statements describing the hypergraph itself, which may be wrapped in a frame
+    let mut body = Vec::new();
+
+    // The node each node is drawn as: itself, or the representative of its unified nodes
+    let rep = match opts.quotient_mode {
+        QuotientMode::Edges => (0..graph.hypergraph.nodes.len()).collect(),
+        QuotientMode::Merge => analysis::quotient_representatives(graph),
+    };
+
+    // Add nodes for each node in the hypergraph
+    body.extend(generate_node_stmts(graph, opts, &rep));
+
+    // Add record nodes for each hyperedge
+    body.extend(generate_edge_stmts(graph, opts));
+
+    // Group operations into lanes
+    if let Some(lane) = &opts.lane {
+        body.extend(generate_lane_stmts(graph, lane));
+    }
+
+    // Place operations into explicit rank layers
+    if let Some(layers) = &opts.layers {
+        body.extend(generate_layer_stmts(graph, layers));
+    }
+
+    // Pin operations to extreme ranks
+    if let Some(rank_pin) = &opts.rank_pin {
+        body.extend(generate_rank_pin_stmts(graph, rank_pin));
+    }
+
+    // Add source and target interface nodes
+    let (inputs, outputs) = if transposed {
+        (&graph.targets, &graph.sources)
+    } else {
+        (&graph.sources, &graph.targets)
+    };
+    let inputs: Vec<lax::NodeId> = inputs
+        .iter()
+        .map(|n| lax::NodeId(representative(&rep, n.0)))
+        .collect();
+    let outputs: Vec<lax::NodeId> = outputs
+        .iter()
+        .map(|n| lax::NodeId(representative(&rep, n.0)))
+        .collect();
+    let mut interface_stmts = if opts.show_interfaces {
+        generate_interface_stmts(&inputs, &outputs, opts)
+    } else {
+        Vec::new()
+    };
+    let interfaces_outside = opts.frame.is_some() && !opts.frame_interfaces;
+    if !interfaces_outside {
+        body.extend(interface_stmts.iter().cloned());
+    }
+
+    // Connect nodes to edges
+    body.extend(generate_connection_stmts(graph, opts, &rep, transposed));
+
+    // Add quotient connections (dotted lines between unified nodes)
+    if opts.quotient_mode == QuotientMode::Edges {
+        body.extend(generate_quotient_stmts(graph, &opts.quotient_style));
+    }
+
+    // Color the shortest path between two nodes
+    if let Some((from, to)) = opts.highlight_path {
+        if let Some(mut path) = analysis::shortest_path(graph, from, to) {
+            // Steps between merged nodes collapse onto their representative
+            for step in path.iter_mut() {
+                if let analysis::PathStep::Node(k) = step {
+                    *k = representative(&rep, *k);
+                }
+            }
+            path.dedup();
+            highlight_path(&mut body, &path, &theme.accent);
+        }
+    }
+
+    if opts.use_layers {
+        assign_layers(&mut body);
+        assign_layers(&mut interface_stmts);
+    }
 
-    // Create a directed graph
-    let mut dot_graph = Graph::DiGraph {
-        id: Id::Plain(String::from("G")),
-        strict: false,
-        stmts: Vec::new(),
+    // Box operations sharing a key into clusters
+    let body = cluster_operations(graph, &opts.cluster_key, theme, body);
+
+    stmts.reserve(body.len() + interface_stmts.len());
+
+    match &opts.frame {
+        Some(title) => stmts.push(generate_frame_stmt(title, theme, body)),
+        None => {
+            for stmt in body {
+                stmts.push(stmt);
+            }
+        }
+    }
+
+    if interfaces_outside {
+        for stmt in interface_stmts {
+            stmts.push(stmt);
+        }
+    }
+
+    // Add a scaled-down overview of the structure, unless there is nothing to show
+    let is_empty = graph.hypergraph.nodes.is_empty() && graph.hypergraph.edges.is_empty();
+    if opts.minimap && !is_empty {
+        stmts.push(generate_minimap_stmt(graph, opts, &rep));
+    }
+
+    // Add a key from labels to colors
+    if let Some(entries) = opts.legend.as_ref().filter(|entries| !entries.is_empty()) {
+        stmts.push(generate_legend_stmt(entries, theme));
+    }
+
+    if !opts.directed {
+        undirect_stmts(&mut stmts);
+    }
+
+    if opts.canonical_order {
+        sort_stmts(&mut stmts);
+    }
+
+    if !opts.id_prefix.is_empty() {
+        prefix_ids(&mut stmts, &opts.id_prefix);
+    }
+
+    if opts.minify {
+        unquote_stmts(&mut stmts);
+    }
+
+    stmts
+}
+
+/// Generates a GraphViz DOT representation of only the wiring of a lax open hypergraph: every
+/// node, the quotient (unification) edges between them, and the interface. Operations and their
+/// connections are omitted, which isolates how wires were merged by `unify`.
+pub fn generate_dot_quotient_only<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> Graph
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    let mut dot_graph = new_graph(opts, generate_header_stmts(graph, opts));
+
+    let interface_stmts = if opts.show_interfaces {
+        generate_interface_stmts(&graph.sources, &graph.targets, opts)
+    } else {
+        Vec::new()
     };
 
+    // Every node is drawn, whatever the quotient mode, since the quotient is the point here
+    let rep: Vec<usize> = (0..graph.hypergraph.nodes.len()).collect();
+
+    let stmts = generate_node_stmts(graph, opts, &rep)
+        .into_iter()
+        .chain(interface_stmts)
+        .chain(generate_quotient_stmts(graph, &opts.quotient_style));
+    for stmt in stmts {
+        dot_graph.add_stmt(stmt);
+    }
+
+    let (Graph::DiGraph { stmts, .. } | Graph::Graph { stmts, .. }) = &mut dot_graph;
+    if !opts.directed {
+        undirect_stmts(stmts);
+    }
+    if opts.canonical_order {
+        sort_stmts(stmts);
+    }
+    if !opts.id_prefix.is_empty() {
+        prefix_ids(stmts, &opts.id_prefix);
+    }
+    if opts.minify {
+        unquote_stmts(stmts);
+    }
+
+    dot_graph
+}
+
+/// Generate the graph-level attributes and default node and edge attributes
+fn generate_header_stmts<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> Vec<Stmt>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    let theme = &opts.theme;
+
+    let mut stmts = Vec::new();
+
     // Set graph attributes
-    dot_graph.add_stmt(Stmt::Attribute(Attribute(
+    stmts.push(Stmt::Attribute(Attribute(
         Id::Plain(String::from("rankdir")),
         Id::Plain(opts.orientation.to_string()),
     )));
 
     // Set background color
-    dot_graph.add_stmt(Stmt::Attribute(Attribute(
+    stmts.push(Stmt::Attribute(Attribute(
         Id::Plain(String::from("bgcolor")),
         Id::Plain(format!("\"{}\"", theme.bgcolor.clone())),
     )));
 
+    // Set output resolution and size
+    if let Some(dpi) = theme.dpi {
+        stmts.push(Stmt::Attribute(Attribute(
+            Id::Plain(String::from("dpi")),
+            Id::Plain(dpi.to_string()),
+        )));
+    }
+    if let Some((width, height)) = theme.size {
+        stmts.push(Stmt::Attribute(Attribute(
+            Id::Plain(String::from("size")),
+            Id::Plain(format!("\"{},{}\"", width, height)),
+        )));
+    }
+
+    // Draw a background grid
+    if let Some(spacing) = opts.background_grid {
+        if let Some(grid) = background_grid(graph, spacing) {
+            stmts.push(Stmt::Attribute(Attribute(
+                Id::Plain(String::from("_background")),
+                Id::Plain(format!("\"{}\"", grid)),
+            )));
+        }
+    }
+
+    // Set node margins, skipping invalid values
+    let separations = [("sep", &opts.sep), ("esep", &opts.esep)];
+    for (name, separation) in separations {
+        if let Some(value) = separation.as_ref().and_then(Separation::to_dot) {
+            stmts.push(Stmt::Attribute(Attribute(
+                Id::Plain(String::from(name)),
+                Id::Plain(format!("\"{}\"", value)),
+            )));
+        }
+    }
+
+    // Set spacing between ranks and between nodes in a rank, skipping invalid values
+    let spacings = [("ranksep", opts.ranksep), ("nodesep", opts.nodesep)];
+    for (name, spacing) in spacings {
+        if let Some(value) = spacing.filter(|x| x.is_finite() && *x >= 0.0) {
+            stmts.push(Stmt::Attribute(Attribute(
+                Id::Plain(String::from(name)),
+                Id::Plain(value.to_string()),
+            )));
+        }
+    }
+
+    // Set overlap removal for force-directed engines
+    if let Some(overlap) = opts.overlap {
+        stmts.push(Stmt::Attribute(Attribute(
+            Id::Plain(String::from("overlap")),
+            Id::Plain(overlap.to_string()),
+        )));
+    }
+
+    // Declare output layers
+    if opts.use_layers {
+        stmts.push(Stmt::Attribute(Attribute(
+            Id::Plain(String::from("layers")),
+            Id::Plain(String::from("\"structure:labels\"")),
+        )));
+    }
+
+    // Set edge routing
+    if let Some(splines) = opts.splines {
+        stmts.push(Stmt::Attribute(Attribute(
+            Id::Plain(String::from("splines")),
+            Id::Plain(splines.to_string()),
+        )));
+    }
+
+    // Merge parallel edges
+    if opts.concentrate {
+        stmts.push(Stmt::Attribute(Attribute(
+            Id::Plain(String::from("concentrate")),
+            Id::Plain(String::from("true")),
+        )));
+    }
+
+    // User-supplied graph attributes
+    for (key, value) in quote_values(&opts.graph_attributes) {
+        stmts.push(Stmt::Attribute(Attribute(Id::Plain(key), Id::Plain(value))));
+    }
+
+    // Typography shared by the node and edge defaults
+    let mut font = Vec::new();
+    if let Some(fontname) = &theme.fontname {
+        font.push(Attribute(
+            Id::Plain(String::from("fontname")),
+            Id::Plain(format!("\"{}\"", fontname)),
+        ));
+    }
+    if let Some(fontsize) = theme.fontsize {
+        font.push(Attribute(
+            Id::Plain(String::from("fontsize")),
+            Id::Plain(fontsize.to_string()),
+        ));
+    }
+
+    // Line thickness shared by nodes and edges
+    let penwidth = theme.penwidth.map(|penwidth| {
+        Attribute(
+            Id::Plain(String::from("penwidth")),
+            Id::Plain(penwidth.to_string()),
+        )
+    });
+
     // Add default node attributes statement
-    dot_graph.add_stmt(Stmt::Node(Node {
+    let mut node_attributes = vec![
+        Attribute(
+            Id::Plain(String::from("shape")),
+            Id::Plain(String::from("record")),
+        ),
+        Attribute(
+            Id::Plain(String::from("style")),
+            Id::Plain(String::from("rounded")),
+        ),
+        Attribute(
+            Id::Plain(String::from("fontcolor")),
+            Id::Plain(format!("\"{}\"", theme.fontcolor.clone())),
+        ),
+        Attribute(
+            Id::Plain(String::from("color")),
+            Id::Plain(format!("\"{}\"", theme.color.clone())),
+        ),
+    ];
+    node_attributes.extend(font.iter().cloned());
+    node_attributes.extend(penwidth.clone());
+    stmts.push(Stmt::Node(Node {
         id: NodeId(Id::Plain(String::from("node")), None),
-        attributes: vec![
-            Attribute(
-                Id::Plain(String::from("shape")),
-                Id::Plain(String::from("record")),
-            ),
-            Attribute(
-                Id::Plain(String::from("style")),
-                Id::Plain(String::from("rounded")),
-            ),
-            Attribute(
-                Id::Plain(String::from("fontcolor")),
-                Id::Plain(format!("\"{}\"", theme.fontcolor.clone())),
-            ),
-            Attribute(
-                Id::Plain(String::from("color")),
-                Id::Plain(format!("\"{}\"", theme.color.clone())),
-            ),
-        ],
+        attributes: node_attributes,
     }));
 
     // Add default edge attributes statement
-    dot_graph.add_stmt(Stmt::Node(Node {
+    let mut edge_attributes = vec![
+        Attribute(
+            Id::Plain(String::from("fontcolor")),
+            Id::Plain(format!("\"{}\"", theme.fontcolor.clone())),
+        ),
+        Attribute(
+            Id::Plain(String::from("color")),
+            Id::Plain(format!("\"{}\"", theme.color.clone())),
+        ),
+        Attribute(
+            Id::Plain(String::from("arrowhead")),
+            Id::Plain(String::from("none")),
+        ),
+    ];
+    edge_attributes.extend(font);
+    edge_attributes.extend(penwidth);
+    stmts.push(Stmt::Node(Node {
         id: NodeId(Id::Plain(String::from("edge")), None),
-        attributes: vec![
-            Attribute(
-                Id::Plain(String::from("fontcolor")),
-                Id::Plain(format!("\"{}\"", theme.fontcolor.clone())),
-            ),
-            Attribute(
-                Id::Plain(String::from("color")),
-                Id::Plain(format!("\"{}\"", theme.color.clone())),
-            ),
-            Attribute(
-                Id::Plain(String::from("arrowhead")),
-                Id::Plain(String::from("none")),
-            ),
-        ],
+        attributes: edge_attributes,
     }));
 
-    // Add nodes for each node in the hypergraph
-    let node_stmts = generate_node_stmts(graph, opts);
-    for stmt in node_stmts {
-        dot_graph.add_stmt(stmt);
+    stmts
+}
+
+/// Wrap statements in a top-level graph, directed unless [`Options::directed`] is unset, with the
+/// id and strictness from `opts`
+fn new_graph<O, A>(opts: &Options<O, A>, stmts: Vec<Stmt>) -> Graph {
+    let id = graph_id(&format!("{}{}", opts.id_prefix, opts.graph_id));
+    if opts.directed {
+        Graph::DiGraph {
+            id,
+            strict: opts.strict,
+            stmts,
+        }
+    } else {
+        Graph::Graph {
+            id,
+            strict: opts.strict,
+            stmts,
+        }
     }
+}
 
-    // Add record nodes for each hyperedge
-    let edge_stmts = generate_edge_stmts(graph, opts);
-    for stmt in edge_stmts {
-        dot_graph.add_stmt(stmt);
+/// Put label-bearing nodes on the `labels` layer and everything else on `structure`
+fn assign_layers(stmts: &mut [Stmt]) {
+    let is_label = |attributes: &[Attribute]| {
+        attributes.iter().any(|Attribute(k, v)| match (k, v) {
+            (Id::Plain(k), Id::Plain(v)) => {
+                (k == "xlabel" && v != "\"\"") || (k == "shape" && v == "plaintext")
+            }
+            _ => false,
+        })
+    };
+
+    for stmt in stmts {
+        let (attributes, layer) = match stmt {
+            // A bare node statement refers to a node declared elsewhere, e.g. placing it in a
+            // rank subgraph, and would override its layer
+            Stmt::Node(node) if node.attributes.is_empty() => continue,
+            Stmt::Node(node) => {
+                let layer = if is_label(&node.attributes) {
+                    "labels"
+                } else {
+                    "structure"
+                };
+                (&mut node.attributes, layer)
+            }
+            Stmt::Edge(edge) => (&mut edge.attributes, "structure"),
+            Stmt::Subgraph(subgraph) => {
+                assign_layers(&mut subgraph.stmts);
+                continue;
+            }
+            _ => continue,
+        };
+        attributes.push(Attribute(
+            Id::Plain(String::from("layer")),
+            Id::Plain(String::from(layer)),
+        ));
     }
+}
 
-    // Add source and target interface nodes
-    let interface_stmts = generate_interface_stmts(graph);
-    for stmt in interface_stmts {
-        dot_graph.add_stmt(stmt);
+/// Generates a separate GraphViz DOT representation for each connected component of a lax open
+/// hypergraph, treating quotient pairs as links.
+///
+/// Each component is reindexed from zero and rendered with its own interface: a global source
+/// or target node appears in the interface of the component containing it, in its original
+/// relative order. Components are ordered by their lowest node index. A dangling node index is
+/// drawn as a phantom node in the component which references it.
+pub fn generate_dot_components<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+) -> Vec<Graph>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    analysis::connected_components(graph)
+        .iter()
+        .map(|component| generate_dot_with(component, opts))
+        .collect()
+}
+
+/// Generates a single GraphViz DOT graph showing several lax open hypergraphs side by side, e.g. a
+/// term before and after a rewrite.
+///
+/// Each graph is drawn as by [`generate_dot_with`] inside a `cluster_<name>` subgraph labelled by
+/// its name, with `<name>_` prepended to its ids (after [`Options::id_prefix`]), so ids don't
+/// collide. Interface nodes stay within their graph's cluster.
+pub fn generate_dot_many<O, A>(
+    graphs: &[(&str, &OpenHypergraph<O, A>)],
+    opts: &Options<O, A>,
+) -> Graph
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    let empty = OpenHypergraph::empty();
+    let mut dot_graph = new_graph(opts, generate_header_stmts(&empty, opts));
+    let header_len = match &dot_graph {
+        Graph::DiGraph { stmts, .. } | Graph::Graph { stmts, .. } => stmts.len(),
+    };
+
+    for (name, graph) in graphs {
+        // Keep everything but the header shared with the enclosing graph
+        let mut stmts: Vec<Stmt> = match generate_dot_with(graph, opts) {
+            Graph::DiGraph { stmts, .. } | Graph::Graph { stmts, .. } => {
+                stmts.into_iter().skip(header_len).collect()
+            }
+        };
+        prefix_ids(&mut stmts, &format!("{}_", name));
+
+        let mut cluster_stmts = vec![
+            Stmt::Attribute(Attribute(
+                Id::Plain(String::from("label")),
+                Id::Plain(format!("\"{}\"", escape_dot_label(name))),
+            )),
+            Stmt::Attribute(Attribute(
+                Id::Plain(String::from("color")),
+                Id::Plain(format!("\"{}\"", opts.theme.color.clone())),
+            )),
+            Stmt::Attribute(Attribute(
+                Id::Plain(String::from("fontcolor")),
+                Id::Plain(format!("\"{}\"", opts.theme.fontcolor.clone())),
+            )),
+        ];
+        cluster_stmts.extend(stmts);
+        dot_graph.add_stmt(Stmt::Subgraph(Subgraph {
+            id: graph_id(&format!("cluster_{}", name)),
+            stmts: cluster_stmts,
+        }));
+    }
+
+    if opts.minify {
+        let (Graph::DiGraph { stmts, .. } | Graph::Graph { stmts, .. }) = &mut dot_graph;
+        unquote_stmts(stmts);
+    }
+
+    dot_graph
+}
+
+/// Generates a GraphViz DOT representation of a strict open hypergraph, by converting it to the
+/// equivalent lax one. Strict hypergraphs have no quotient, so no quotient edges are drawn.
+pub fn generate_dot_strict<O, A>(
+    graph: &strict::OpenHypergraph<VecKind, O, A>,
+    opts: &Options<O, A>,
+) -> Graph
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    generate_dot_with(&OpenHypergraph::from_strict(graph.clone()), opts)
+}
+
+/// Generates a GraphViz DOT representation of a lax open hypergraph after applying the
+/// [`Forget`](open_hypergraphs::lax::var::forget::Forget) functor, which replaces the `Copy`-like
+/// operations created by the `var` interface (those labelled [`HasVar::var`]) with plain wires.
+pub fn generate_dot_forget<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> Graph
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + HasVar + MaybeSync,
+{
+    generate_dot_with(&forget::forget(graph), opts)
+}
+
+/// Faint grid color; translucent so it works on light and dark backgrounds
+const GRID_COLOR: &str = "#80808040";
+
+/// Build xdot drawing operations for a grid of lines `spacing` inches apart.
+/// Returns `None` for non-positive spacing.
+fn background_grid<O, A>(graph: &OpenHypergraph<O, A>, spacing: f64) -> Option<String> {
+    if !(spacing.is_finite() && spacing > 0.0) {
+        return None;
+    }
+
+    // Estimate the extent (in points) of the laid-out graph: an inch per element, plus margin
+    let elements = graph.hypergraph.nodes.len() + graph.hypergraph.edges.len();
+    let extent = 72.0 * (elements + 2) as f64;
+    let step = 72.0 * spacing;
+    let lines = (extent / step).ceil() as usize;
+
+    let mut ops = format!("c {} -{} ", GRID_COLOR.len() + 1, GRID_COLOR);
+    for k in 0..=lines {
+        let x = k as f64 * step;
+        ops.push_str(&format!("L 2 {x} 0 {x} {extent} "));
+        ops.push_str(&format!("L 2 0 {x} {extent} {x} "));
+    }
+
+    Some(ops.trim_end().to_string())
+}
+
+/// Move the node statement of each operation with a `Some` key into a bordered `cluster_op_k`
+/// subgraph labelled by the key. Clusters are numbered and appended in order of their first
+/// operation; all other statements are kept in place.
+fn cluster_operations<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    key: &EdgeKey<A>,
+    theme: &Theme,
+    stmts: Vec<Stmt>,
+) -> Vec<Stmt> {
+    let keys: Vec<Option<String>> = graph.hypergraph.edges.iter().map(key).collect();
+    if keys.iter().all(Option::is_none) {
+        return stmts;
     }
 
-    // Connect nodes to edges
-    let connection_stmts = generate_connection_stmts(graph);
-    for stmt in connection_stmts {
-        dot_graph.add_stmt(stmt);
+    let mut clusters: Vec<(String, Vec<Stmt>)> = Vec::new();
+    let mut cluster_index: HashMap<String, usize> = HashMap::new();
+    let mut rest = Vec::with_capacity(stmts.len());
+    for stmt in stmts {
+        let edge = match &stmt {
+            Stmt::Node(Node {
+                id: NodeId(Id::Plain(id), _),
+                ..
+            }) => id.strip_prefix("e_").and_then(|i| i.parse::<usize>().ok()),
+            _ => None,
+        };
+        match edge.and_then(|i| keys.get(i).cloned().flatten()) {
+            Some(k) => {
+                let index = *cluster_index.entry(k.clone()).or_insert_with(|| {
+                    clusters.push((k, Vec::new()));
+                    clusters.len() - 1
+                });
+                clusters[index].1.push(stmt);
+            }
+            None => rest.push(stmt),
+        }
+    }
+
+    for (index, (k, members)) in clusters.into_iter().enumerate() {
+        let mut cluster_stmts = vec![
+            Stmt::Attribute(Attribute(
+                Id::Plain(String::from("label")),
+                Id::Plain(format!("\"{}\"", escape_dot_label(&k))),
+            )),
+            Stmt::Attribute(Attribute(
+                Id::Plain(String::from("color")),
+                Id::Plain(format!("\"{}\"", theme.color.clone())),
+            )),
+            Stmt::Attribute(Attribute(
+                Id::Plain(String::from("fontcolor")),
+                Id::Plain(format!("\"{}\"", theme.fontcolor.clone())),
+            )),
+        ];
+        cluster_stmts.extend(members);
+        rest.push(Stmt::Subgraph(Subgraph {
+            id: Id::Plain(format!("cluster_op_{}", index)),
+            stmts: cluster_stmts,
+        }));
+    }
+
+    rest
+}
+
+/// Wrap statements in a bordered, titled `cluster_frame` subgraph
+fn generate_frame_stmt(title: &str, theme: &Theme, stmts: Vec<Stmt>) -> Stmt {
+    let mut frame_stmts = vec![
+        Stmt::Attribute(Attribute(
+            Id::Plain(String::from("label")),
+            Id::Plain(format!("\"{}\"", escape_dot_label(title))),
+        )),
+        Stmt::Attribute(Attribute(
+            Id::Plain(String::from("color")),
+            Id::Plain(format!("\"{}\"", theme.accent.clone())),
+        )),
+        Stmt::Attribute(Attribute(
+            Id::Plain(String::from("fontcolor")),
+            Id::Plain(format!("\"{}\"", theme.fontcolor.clone())),
+        )),
+    ];
+    frame_stmts.extend(stmts);
+
+    Stmt::Subgraph(Subgraph {
+        id: Id::Plain(String::from("cluster_frame")),
+        stmts: frame_stmts,
+    })
+}
+
+/// Generate a `cluster_legend` subgraph with a filled swatch node for each `(label, color)` entry.
+/// Entries are stacked by invisible edges, and nothing connects them to the rest of the graph.
+fn generate_legend_stmt(entries: &[(String, String)], theme: &Theme) -> Stmt {
+    let mut stmts = vec![
+        Stmt::Attribute(Attribute(
+            Id::Plain(String::from("label")),
+            Id::Plain(String::from("\"Legend\"")),
+        )),
+        Stmt::Attribute(Attribute(
+            Id::Plain(String::from("color")),
+            Id::Plain(format!("\"{}\"", theme.accent.clone())),
+        )),
+        Stmt::Attribute(Attribute(
+            Id::Plain(String::from("fontcolor")),
+            Id::Plain(format!("\"{}\"", theme.fontcolor.clone())),
+        )),
+    ];
+
+    for (k, (label, color)) in entries.iter().enumerate() {
+        stmts.push(Stmt::Node(Node {
+            id: NodeId(Id::Plain(format!("legend_{}", k)), None),
+            attributes: vec![
+                Attribute(
+                    Id::Plain(String::from("shape")),
+                    Id::Plain(String::from("box")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("style")),
+                    Id::Plain(String::from("\"rounded,filled\"")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("fillcolor")),
+                    Id::Plain(format!("\"{}\"", color)),
+                ),
+                Attribute(
+                    Id::Plain(String::from("label")),
+                    Id::Plain(format!("\"{}\"", escape_dot_label(label))),
+                ),
+            ],
+        }));
+    }
+
+    for k in 1..entries.len() {
+        stmts.push(Stmt::Edge(Edge {
+            ty: EdgeTy::Pair(
+                Vertex::N(NodeId(Id::Plain(format!("legend_{}", k - 1)), None)),
+                Vertex::N(NodeId(Id::Plain(format!("legend_{}", k)), None)),
+            ),
+            attributes: vec![Attribute(
+                Id::Plain(String::from("style")),
+                Id::Plain(String::from("invis")),
+            )],
+        }));
+    }
+
+    Stmt::Subgraph(Subgraph {
+        id: Id::Plain(String::from("cluster_legend")),
+        stmts,
+    })
+}
+
+/// Generate a `cluster_minimap` subgraph holding a tiny unlabelled copy of the nodes, operations,
+/// connections and quotient edges, with ids prefixed by `m_`. Merged nodes and quotient edges
+/// follow `opts.quotient_mode`, as in the main drawing.
+fn generate_minimap_stmt<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+    rep: &[usize],
+) -> Stmt {
+    let theme = &opts.theme;
+    let mut stmts = vec![
+        Stmt::Attribute(Attribute(
+            Id::Plain(String::from("style")),
+            Id::Plain(String::from("dashed")),
+        )),
+        Stmt::Attribute(Attribute(
+            Id::Plain(String::from("color")),
+            Id::Plain(format!("\"{}\"", theme.accent.clone())),
+        )),
+        Stmt::Node(Node {
+            id: NodeId(Id::Plain(String::from("edge")), None),
+            attributes: vec![Attribute(
+                Id::Plain(String::from("penwidth")),
+                Id::Plain(String::from("0.3")),
+            )],
+        }),
+    ];
+
+    // Merged nodes are drawn as their representative
+    for i in (0..graph.hypergraph.nodes.len()).filter(|&i| rep[i] == i) {
+        stmts.push(Stmt::Node(Node {
+            id: NodeId(Id::Plain(format!("m_n_{}", i)), None),
+            attributes: vec![
+                Attribute(
+                    Id::Plain(String::from("shape")),
+                    Id::Plain(String::from("point")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("width")),
+                    Id::Plain(String::from("0.02")),
+                ),
+            ],
+        }));
+    }
+
+    for i in 0..graph.hypergraph.edges.len() {
+        stmts.push(Stmt::Node(Node {
+            id: NodeId(Id::Plain(format!("m_e_{}", i)), None),
+            attributes: vec![
+                Attribute(
+                    Id::Plain(String::from("shape")),
+                    Id::Plain(String::from("box")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("label")),
+                    Id::Plain(String::from("\"\"")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("width")),
+                    Id::Plain(String::from("0.1")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("height")),
+                    Id::Plain(String::from("0.05")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("fixedsize")),
+                    Id::Plain(String::from("true")),
+                ),
+            ],
+        }));
+    }
+
+    let mini_edge = |from: String, to: String, attributes: Vec<Attribute>| {
+        Stmt::Edge(Edge {
+            ty: EdgeTy::Pair(
+                Vertex::N(NodeId(Id::Plain(from), None)),
+                Vertex::N(NodeId(Id::Plain(to), None)),
+            ),
+            attributes,
+        })
+    };
+
+    for (i, hyperedge) in graph.hypergraph.adjacency.iter().enumerate() {
+        for node_id in &hyperedge.sources {
+            stmts.push(mini_edge(
+                format!("m_n_{}", representative(rep, node_id.0)),
+                format!("m_e_{}", i),
+                vec![],
+            ));
+        }
+        for node_id in &hyperedge.targets {
+            stmts.push(mini_edge(
+                format!("m_e_{}", i),
+                format!("m_n_{}", representative(rep, node_id.0)),
+                vec![],
+            ));
+        }
+    }
+
+    if opts.quotient_mode == QuotientMode::Edges {
+        for (left, right) in quotient_pairs(graph) {
+            stmts.push(mini_edge(
+                format!("m_n_{}", left),
+                format!("m_n_{}", right),
+                vec![Attribute(
+                    Id::Plain(String::from("style")),
+                    Id::Plain(String::from("dotted")),
+                )],
+            ));
+        }
+    }
+
+    Stmt::Subgraph(Subgraph {
+        id: Id::Plain(String::from("cluster_minimap")),
+        stmts,
+    })
+}
+
+// Unfortunately this seems to be a fundamental limitation of the dot syntax;
+// See https://forum.graphviz.org/t/how-do-i-properly-escape-arbitrary-text-for-use-in-labels/1762
+// > Unfortunately, due to past mistakes, we realized there is no way to safely put
+// > arbitrary text in graphviz strings, as we made mistakes in handling quotes and escapes.
+/// Escape text for use inside a quoted DOT string: the default for [`Options::escape`]
+pub fn escape_dot_label(s: &str) -> String {
+    s.chars()
+        .flat_map(|c| match c {
+            '\\' => Some("\\\\".to_string()),
+            '"' => Some("\\\"".to_string()),
+            '{' => Some("\\{".to_string()),
+            '}' => Some("\\}".to_string()),
+            '|' => Some("\\|".to_string()),
+            '<' => Some("\\<".to_string()),
+            '>' => Some("\\>".to_string()),
+            // A centered line break in the rendered label
+            '\n' => Some("\\n".to_string()),
+            // GraphViz has no tab escape, so use spaces instead
+            '\t' => Some("    ".to_string()),
+            // Drop carriage returns, so `\r\n` is a single line break
+            '\r' => None,
+            _ => Some(c.to_string()),
+        })
+        .collect()
+}
+
+/// Break each line of `label` longer than `max_width` characters at the last space which keeps it
+/// within the width. Words longer than the width are kept whole on their own line.
+fn wrap_label(label: &str, max_width: Option<usize>) -> String {
+    let Some(max_width) = max_width else {
+        return label.to_string();
+    };
+
+    let mut lines = Vec::new();
+    for line in label.split('\n') {
+        if line.chars().count() <= max_width {
+            lines.push(line.to_string());
+            continue;
+        }
+        let mut current = String::new();
+        for word in line.split_whitespace() {
+            let width = current.chars().count();
+            if width > 0 && width + 1 + word.chars().count() > max_width {
+                lines.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        lines.push(current);
+    }
+    lines.join("\n")
+}
+
+/// The id of the top-level graph, quoted unless it is a valid plain identifier
+fn graph_id(id: &str) -> Id {
+    if is_plain_id(id) {
+        Id::Plain(id.to_string())
+    } else {
+        Id::Plain(format!("\"{}\"", id.replace('"', "\\\"")))
+    }
+}
+
+/// Keywords of the DOT language, which can't be used as unquoted identifiers
+const DOT_KEYWORDS: [&str; 6] = ["node", "edge", "graph", "digraph", "subgraph", "strict"];
+
+/// Whether `s` is a valid unquoted DOT identifier, i.e. matches `[a-zA-Z_][a-zA-Z0-9_]*` and is
+/// not a keyword
+fn is_plain_id(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !DOT_KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(s))
+}
+
+/// Remove the quotes from `id` if its contents form a valid unquoted DOT identifier
+fn unquote_id(id: &mut Id) {
+    if let Id::Plain(s) = id {
+        let Some(inner) = s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) else {
+            return;
+        };
+        if is_plain_id(inner) {
+            *s = inner.to_string();
+        }
+    }
+}
+
+/// Unquote every attribute value in `stmts` which doesn't need quoting, recursing into subgraphs
+fn unquote_stmts(stmts: &mut [Stmt]) {
+    for stmt in stmts {
+        let attributes = match stmt {
+            Stmt::Node(node) => &mut node.attributes,
+            Stmt::Edge(edge) => &mut edge.attributes,
+            Stmt::Attribute(attribute) => {
+                unquote_id(&mut attribute.1);
+                continue;
+            }
+            Stmt::Subgraph(subgraph) => {
+                unquote_stmts(&mut subgraph.stmts);
+                continue;
+            }
+            Stmt::GAttribute(_) => continue,
+        };
+        for Attribute(_, value) in attributes {
+            unquote_id(value);
+        }
+    }
+}
+
+/// Drop the attributes giving edges in `stmts` a direction (`dir`, `arrowhead` and `arrowtail`),
+/// recursing into subgraphs. Edges keep their tail-to-head order, so ranking is unchanged.
+fn undirect_stmts(stmts: &mut [Stmt]) {
+    for stmt in stmts {
+        match stmt {
+            Stmt::Edge(edge) => edge.attributes.retain(|Attribute(key, _)| {
+                !matches!(key, Id::Plain(k) if k == "dir" || k == "arrowhead" || k == "arrowtail")
+            }),
+            Stmt::Subgraph(subgraph) => undirect_stmts(&mut subgraph.stmts),
+            _ => {}
+        }
+    }
+}
+
+/// Whether a node id is `node`, `edge` or `graph`, which mark default attribute statements such
+/// as `node[shape=record]` rather than actual nodes
+fn is_default_attributes(id: &Id) -> bool {
+    matches!(id, Id::Plain(s) if s == "node" || s == "edge" || s == "graph")
+}
+
+/// Sort `stmts` into the order documented on [`Options::canonical_order`], recursing into
+/// subgraphs. The sort is stable, so attribute statements keep their relative order.
+fn sort_stmts(stmts: &mut [Stmt]) {
+    // Split an id into runs of text and numbers, so `n_2` sorts before `n_10`
+    fn natural_key(id: &str) -> Vec<(String, u64)> {
+        let mut key = Vec::new();
+        let mut text = String::new();
+        let mut number = None;
+        for c in id.chars() {
+            match (c.to_digit(10), number) {
+                (Some(d), n) => {
+                    number = Some(
+                        n.unwrap_or(0u64)
+                            .saturating_mul(10)
+                            .saturating_add(d.into()),
+                    )
+                }
+                (None, Some(n)) => {
+                    key.push((std::mem::take(&mut text), n));
+                    number = None;
+                    text.push(c);
+                }
+                (None, None) => text.push(c),
+            }
+        }
+        key.push((text, number.unwrap_or(0)));
+        key
+    }
+
+    fn vertex_key(vertex: &Vertex) -> Vec<(String, u64)> {
+        match vertex {
+            Vertex::N(NodeId(id, None)) => natural_key(&id.to_string()),
+            Vertex::N(NodeId(id, Some(Port(port, compass)))) => {
+                let port = port.as_ref().map(|p| p.to_string()).unwrap_or_default();
+                let compass = compass.as_deref().unwrap_or_default();
+                natural_key(&format!("{}:{}:{}", id, port, compass))
+            }
+            Vertex::S(subgraph) => natural_key(&subgraph.id.to_string()),
+        }
+    }
+
+    for stmt in stmts.iter_mut() {
+        if let Stmt::Subgraph(subgraph) = stmt {
+            sort_stmts(&mut subgraph.stmts);
+        }
+    }
+
+    stmts.sort_by_cached_key(|stmt| match stmt {
+        Stmt::Attribute(_) | Stmt::GAttribute(_) => (0, vec![], vec![]),
+        Stmt::Node(node) => {
+            if is_default_attributes(&node.id.0) {
+                return (0, vec![], vec![]);
+            }
+            let id = node.id.0.to_string();
+            let class = if id.starts_with("n_") {
+                1
+            } else if id.starts_with("e_") {
+                2
+            } else {
+                3
+            };
+            (class, natural_key(&id), vec![])
+        }
+        Stmt::Subgraph(subgraph) => (4, natural_key(&subgraph.id.to_string()), vec![]),
+        Stmt::Edge(edge) => match &edge.ty {
+            EdgeTy::Pair(from, to) => (5, vertex_key(from), vertex_key(to)),
+            EdgeTy::Chain(vertices) => (
+                5,
+                vertices.first().map(vertex_key).unwrap_or_default(),
+                vertices.get(1).map(vertex_key).unwrap_or_default(),
+            ),
+        },
+    });
+}
+
+/// Prepend `prefix` to every node and subgraph id in `stmts`, recursing into subgraphs. Cluster
+/// ids keep their leading `cluster` so GraphViz still draws them as clusters.
+fn prefix_ids(stmts: &mut [Stmt], prefix: &str) {
+    fn prefix_id(id: &mut Id, prefix: &str) {
+        if let Id::Plain(s) = id {
+            // An id which is already quoted is prefixed inside its quotes, and is already escaped
+            let quoted = s.strip_prefix('"').and_then(|s| s.strip_suffix('"'));
+            let name = quoted.unwrap_or(s);
+            let prefixed = match name.strip_prefix("cluster") {
+                Some(rest) => format!("cluster{}{}", prefix, rest),
+                None => format!("{}{}", prefix, name),
+            };
+            *s = if is_plain_id(&prefixed) {
+                prefixed
+            } else if quoted.is_some() {
+                format!("\"{}\"", prefixed)
+            } else {
+                format!("\"{}\"", prefixed.replace('"', "\\\""))
+            };
+        }
+    }
+
+    fn prefix_vertex(vertex: &mut Vertex, prefix: &str) {
+        match vertex {
+            Vertex::N(NodeId(id, _)) => prefix_id(id, prefix),
+            Vertex::S(subgraph) => {
+                prefix_id(&mut subgraph.id, prefix);
+                prefix_ids(&mut subgraph.stmts, prefix);
+            }
+        }
+    }
+
+    for stmt in stmts {
+        match stmt {
+            // Default attribute statements keep their reserved ids
+            Stmt::Node(node) if is_default_attributes(&node.id.0) => {}
+            Stmt::Node(node) => prefix_id(&mut node.id.0, prefix),
+            Stmt::Edge(edge) => match &mut edge.ty {
+                EdgeTy::Pair(from, to) => {
+                    prefix_vertex(from, prefix);
+                    prefix_vertex(to, prefix);
+                }
+                EdgeTy::Chain(vertices) => {
+                    for vertex in vertices {
+                        prefix_vertex(vertex, prefix);
+                    }
+                }
+            },
+            Stmt::Subgraph(subgraph) => {
+                prefix_id(&mut subgraph.id, prefix);
+                prefix_ids(&mut subgraph.stmts, prefix);
+            }
+            Stmt::Attribute(_) | Stmt::GAttribute(_) => {}
+        }
+    }
+}
+
+/// Escape text for use in an HTML-like label, where only the XML special characters matter
+fn escape_dot_html(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&#39;".to_string(),
+            '\n' => "<BR/>".to_string(),
+            '\t' => "    ".to_string(),
+            '\r' => "".to_string(),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+/// Set each `(key, value)` pair on `attributes`, replacing any existing attribute with that key
+fn override_attributes(attributes: &mut Vec<Attribute>, pairs: Vec<(String, String)>) {
+    for (key, value) in pairs {
+        let key = Id::Plain(key);
+        match attributes.iter_mut().find(|Attribute(k, _)| *k == key) {
+            Some(attribute) => attribute.1 = Id::Plain(value),
+            None => attributes.push(Attribute(key, Id::Plain(value))),
+        }
+    }
+}
+
+/// Quote the value of each pair, escaping embedded quotes
+fn quote_values(pairs: &[(String, String)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.clone(), format!("\"{}\"", v.replace('"', "\\\""))))
+        .collect()
+}
+
+/// The unescaped label of node `i`, as configured by the labelling and numbering options
+pub(crate) fn node_label_text<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+    i: usize,
+) -> String {
+    let label = match &opts.node_label_graph {
+        Some(node_label) => node_label(graph, i),
+        None => opts.node_label.label(&graph.hypergraph.nodes[i]),
+    };
+    if opts.number_nodes {
+        format!("[{}] {}", i, label)
+    } else {
+        label
+    }
+}
+
+/// The unescaped label of operation `i`, as configured by the labelling, placeholder and
+/// numbering options
+pub(crate) fn edge_label_text<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+    i: usize,
+) -> String {
+    let label = match &opts.edge_label_graph {
+        Some(edge_label) => edge_label(graph, i),
+        None => opts.edge_label.label(&graph.hypergraph.edges[i]),
+    };
+    let label = match &opts.empty_label_placeholder {
+        Some(placeholder) if label.is_empty() => placeholder.clone(),
+        _ => label,
+    };
+    if opts.number_operations {
+        format!("[{}] {}", i, label)
+    } else {
+        label
+    }
+}
+
+/// Concatenate the statements `f(i)` for each `i` in `0..count`, in order. With the `rayon`
+/// feature these are generated in parallel.
+#[cfg(feature = "rayon")]
+fn concat_indexed<F>(count: usize, f: F) -> Vec<Stmt>
+where
+    F: Fn(usize) -> Vec<Stmt> + Send + Sync,
+{
+    use rayon::prelude::*;
+    (0..count).into_par_iter().flat_map_iter(f).collect()
+}
+
+/// Concatenate the statements `f(i)` for each `i` in `0..count`, in order. With the `rayon`
+/// feature these are generated in parallel.
+#[cfg(not(feature = "rayon"))]
+fn concat_indexed<F>(count: usize, f: F) -> Vec<Stmt>
+where
+    F: Fn(usize) -> Vec<Stmt>,
+{
+    let mut stmts = Vec::with_capacity(count);
+    for i in 0..count {
+        stmts.extend(f(i));
+    }
+    stmts
+}
+
+/// Generate node statements for each node in the hypergraph which is its own representative
+fn generate_node_stmts<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+    rep: &[usize],
+) -> Vec<Stmt>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    concat_indexed(graph.hypergraph.nodes.len(), |i| {
+        // Merged nodes are drawn as their representative
+        if rep[i] != i {
+            return Vec::new();
+        }
+
+        // Escape special dot characters.
+        let label = node_label_text(graph, opts, i);
+        let label = (opts.escape)(&wrap_label(&label, opts.label_max_width));
+
+        let is_state = opts
+            .node_is_state
+            .as_ref()
+            .is_some_and(|f| f(&graph.hypergraph.nodes[i]));
+
+        // Wire nodes with a label can show it in place of the point
+        let inline =
+            !is_state && opts.node_label_placement == LabelPlacement::Inline && !label.is_empty();
+
+        let mut attributes = if is_state {
+            // State nodes are drawn as a small fixed-size marker instead of a point
+            vec![
+                Attribute(
+                    Id::Plain(String::from("shape")),
+                    Id::Plain(opts.state_node_shape.clone()),
+                ),
+                Attribute(
+                    Id::Plain(String::from("label")),
+                    Id::Plain(String::from("\"\"")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("width")),
+                    Id::Plain(String::from("0.15")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("height")),
+                    Id::Plain(String::from("0.15")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("fixedsize")),
+                    Id::Plain(String::from("true")),
+                ),
+            ]
+        } else if inline {
+            // The label itself stands in for the point, with wires meeting at its edges
+            vec![
+                Attribute(
+                    Id::Plain(String::from("shape")),
+                    Id::Plain(String::from("plaintext")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("label")),
+                    Id::Plain(format!("\"{}\"", label)),
+                ),
+                Attribute(
+                    Id::Plain(String::from("margin")),
+                    Id::Plain(String::from("0")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("width")),
+                    Id::Plain(String::from("0")),
+                ),
+                Attribute(
+                    Id::Plain(String::from("height")),
+                    Id::Plain(String::from("0")),
+                ),
+            ]
+        } else {
+            vec![Attribute(
+                Id::Plain(String::from("shape")),
+                Id::Plain(String::from("point")),
+            )]
+        };
+        // An empty xlabel can still take up space in the layout, so leave it out
+        if !label.is_empty() && !inline {
+            attributes.push(Attribute(
+                Id::Plain(String::from("xlabel")),
+                Id::Plain(format!("\"{}\"", label)),
+            ));
+        }
+
+        let style = (opts.node_style)(&graph.hypergraph.nodes[i]);
+        override_attributes(&mut attributes, style.to_pairs());
+        let extra = (opts.node_attributes)(&graph.hypergraph.nodes[i]);
+        override_attributes(&mut attributes, quote_values(&extra));
+
+        // Highlight boundary nodes
+        if let Some(style) = &opts.source_node_style {
+            if graph.sources.iter().any(|n| n.0 == i) {
+                override_attributes(&mut attributes, style.to_pairs());
+            }
+        }
+        if let Some(style) = &opts.target_node_style {
+            if graph.targets.iter().any(|n| n.0 == i) {
+                override_attributes(&mut attributes, style.to_pairs());
+            }
+        }
+
+        if opts.highlight_nodes.contains(&i) {
+            override_attributes(&mut attributes, highlight_node_attributes(&opts.theme));
+        }
+
+        if let Some(extra) = opts.extra_node_attributes.get(&i) {
+            override_attributes(&mut attributes, quote_values(extra));
+        }
+
+        let mut stmts = vec![Stmt::Node(Node {
+            id: NodeId(Id::Plain(format!("n_{}", i)), None),
+            attributes,
+        })];
+
+        if let Some(extra) = &opts.extra_node_stmts {
+            stmts.extend(extra(i, &graph.hypergraph.nodes[i]));
+        }
+
+        stmts
+    })
+}
+
+/// Generate record node statements for each hyperedge
+fn generate_edge_stmts<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> Vec<Stmt>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    concat_indexed(graph.hypergraph.edges.len(), |i| {
+        let mut attributes = if let Some(color) = (opts.is_spider)(&graph.hypergraph.edges[i]) {
+            // Spiders are drawn as a filled circle instead of a record
+            spider_attributes(&color)
+        } else if (opts.is_copy)(&graph.hypergraph.edges[i]) {
+            // Copies are drawn as a small dot
+            copy_attributes()
+        } else {
+            record_attributes(graph, opts, i)
+        };
+
+        if opts.highlight_edges.contains(&i) {
+            override_attributes(&mut attributes, highlight_attributes(&opts.theme));
+        }
+        if let Some(extra) = opts.extra_edge_attributes.get(&i) {
+            override_attributes(&mut attributes, quote_values(extra));
+        }
+
+        let mut stmts = vec![Stmt::Node(Node {
+            id: NodeId(Id::Plain(format!("e_{}", i)), None),
+            attributes,
+        })];
+
+        if let Some(extra) = &opts.extra_edge_stmts {
+            stmts.extend(extra(i, &graph.hypergraph.edges[i]));
+        }
+
+        stmts
+    })
+}
+
+/// Attributes of the record node for an operation which isn't a spider or copy: its label and
+/// ports, shape and style
+fn record_attributes<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+    i: usize,
+) -> Vec<Attribute>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    let hyperedge = &graph.hypergraph.adjacency[i];
+    let label = wrap_label(&edge_label_text(graph, opts, i), opts.label_max_width);
+
+    let (label, shape) = match opts.operation_display {
+        // HTML labels draw their own table, so the node itself has no outline
+        OperationDisplay::Ports if opts.html_labels => {
+            let (sources, targets) = port_labels(graph, opts, hyperedge, &escape_dot_html);
+            (
+                Id::Html(html_label(
+                    &escape_dot_html(&label),
+                    &sources,
+                    &targets,
+                    opts,
+                )),
+                String::from("plain"),
+            )
+        }
+        OperationDisplay::Ports => {
+            let (sources, targets) = port_labels(graph, opts, hyperedge, &*opts.escape);
+            (
+                Id::Plain(record_label(
+                    &(opts.escape)(&label),
+                    &sources,
+                    &targets,
+                    opts,
+                )),
+                opts.edge_shape.to_string(),
+            )
+        }
+        // Compact operations show their arity instead of port cells
+        OperationDisplay::CompactArity => (
+            Id::Plain(format!(
+                "\"{} ({}→{})\"",
+                (opts.escape)(&label),
+                hyperedge.sources.len(),
+                hyperedge.targets.len()
+            )),
+            opts.edge_shape.to_string(),
+        ),
+    };
+
+    let mut attributes = vec![
+        Attribute(Id::Plain(String::from("label")), label),
+        Attribute(Id::Plain(String::from("shape")), Id::Plain(shape)),
+    ];
+    if let Some(fillcolor) = (opts.edge_color)(&graph.hypergraph.edges[i]) {
+        let fill = OperationStyle {
+            fillcolor: Some(fillcolor),
+            ..Default::default()
+        };
+        override_attributes(&mut attributes, fill.to_pairs());
+    }
+    let style = (opts.edge_style)(&graph.hypergraph.edges[i]);
+    override_attributes(&mut attributes, style.to_pairs());
+    let extra = (opts.edge_attributes)(&graph.hypergraph.edges[i]);
+    override_attributes(&mut attributes, quote_values(&extra));
+    attributes
+}
+
+/// The escaped text shown in each source and target port cell of an operation: the label of the
+/// connected wire node with `label_ports`, and empty otherwise
+fn port_labels<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+    hyperedge: &Hyperedge,
+    escape: &dyn Fn(&str) -> String,
+) -> (Vec<String>, Vec<String>) {
+    let labels = |nodes: &[lax::NodeId]| -> Vec<String> {
+        nodes
+            .iter()
+            .map(|node| {
+                if opts.label_ports && node.0 < graph.hypergraph.nodes.len() {
+                    escape(&node_label_text(graph, opts, node.0))
+                } else {
+                    String::new()
+                }
+            })
+            .collect()
+    };
+    (labels(&hyperedge.sources), labels(&hyperedge.targets))
+}
+
+/// Build the quoted record label for an operation: its label between cells for the source and
+/// target ports, which show the given (escaped) port labels
+fn record_label<O, A>(
+    label: &str,
+    source_labels: &[String],
+    target_labels: &[String],
+    opts: &Options<O, A>,
+) -> String {
+    // Create port sections for sources
+    let num_sources = source_labels.len();
+    let shown_sources = shown_ports(num_sources, opts.max_ports_shown);
+    let mut source_ports = String::new();
+    for (j, port_label) in source_labels.iter().take(shown_sources).enumerate() {
+        source_ports.push_str(&format!("<s_{j}> "));
+        if !port_label.is_empty() {
+            source_ports.push_str(&format!("{port_label} "));
+        }
+        source_ports.push_str("| ");
+    }
+    if shown_sources < num_sources {
+        let hidden = num_sources - shown_sources;
+        source_ports.push_str(&format!("<s_more> +{hidden} more | "));
+    }
+    if !source_ports.is_empty() {
+        source_ports.truncate(source_ports.len() - 3); // Remove last " | "
+    }
+
+    // Create port sections for targets
+    let num_targets = target_labels.len();
+    let shown_targets = shown_ports(num_targets, opts.max_ports_shown);
+    let mut target_ports = String::new();
+    for (j, port_label) in target_labels.iter().take(shown_targets).enumerate() {
+        target_ports.push_str(&format!("<t_{j}> "));
+        if !port_label.is_empty() {
+            target_ports.push_str(&format!("{port_label} "));
+        }
+        target_ports.push_str("| ");
+    }
+    if shown_targets < num_targets {
+        let hidden = num_targets - shown_targets;
+        target_ports.push_str(&format!("<t_more> +{hidden} more | "));
+    }
+    if !target_ports.is_empty() {
+        target_ports.truncate(target_ports.len() - 3); // Remove last " | "
+    }
+
+    // Create full record label with proper quoting for GraphViz DOT format
+    if source_ports.is_empty() && target_ports.is_empty() {
+        return format!("\"{}\"", label);
+    }
+    let sections: Vec<String> = [
+        (!source_ports.is_empty()).then(|| format!("{{ {} }}", source_ports)),
+        Some(label.to_string()),
+        (!target_ports.is_empty()).then(|| format!("{{ {} }}", target_ports)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    match opts.record_layout {
+        RecordLayout::Stacked => format!("\"{{ {} }}\"", sections.join(" | ")),
+        RecordLayout::Spread => format!("\"{}\"", sections.join(" | ")),
+    }
+}
+
+/// Build the HTML-like label for an operation: a table with its label between cells for the
+/// source and target ports, which show the given (escaped) port labels, laid out along the flow
+/// of `opts.orientation`
+fn html_label<O, A>(
+    label: &str,
+    source_labels: &[String],
+    target_labels: &[String],
+    opts: &Options<O, A>,
+) -> String {
+    let along_rows = matches!(opts.orientation, Orientation::TB | Orientation::BT);
+
+    // A nested table holding one cell per shown port, and a summary cell for the rest
+    let ports_table = |prefix: &str, port_labels: &[String]| -> Option<String> {
+        let count = port_labels.len();
+        if count == 0 {
+            return None;
+        }
+        let shown = shown_ports(count, opts.max_ports_shown);
+        let mut cells: Vec<String> = port_labels
+            .iter()
+            .take(shown)
+            .enumerate()
+            .map(|(j, port_label)| format!("<TD PORT=\"{}_{}\">{}</TD>", prefix, j, port_label))
+            .collect();
+        if shown < count {
+            cells.push(format!(
+                "<TD PORT=\"{}_more\">+{} more</TD>",
+                prefix,
+                count - shown
+            ));
+        }
+        let rows = if along_rows {
+            format!("<TR>{}</TR>", cells.concat())
+        } else {
+            cells.iter().map(|c| format!("<TR>{}</TR>", c)).collect()
+        };
+        Some(format!(
+            "<TABLE BORDER=\"0\" CELLBORDER=\"1\" CELLSPACING=\"0\">{}</TABLE>",
+            rows
+        ))
+    };
+
+    let mut sections: Vec<String> = [
+        ports_table("s", source_labels),
+        Some(label.to_string()),
+        ports_table("t", target_labels),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    // Flow runs against the reading order, so targets come first
+    if matches!(opts.orientation, Orientation::RL | Orientation::BT) {
+        sections.reverse();
+    }
+
+    let cells: Vec<String> = sections
+        .iter()
+        .map(|section| format!("<TD>{}</TD>", section))
+        .collect();
+    let rows = if along_rows {
+        cells.iter().map(|c| format!("<TR>{}</TR>", c)).collect()
+    } else {
+        format!("<TR>{}</TR>", cells.concat())
+    };
+
+    format!(
+        "<<TABLE BORDER=\"1\" CELLBORDER=\"0\" CELLSPACING=\"0\" STYLE=\"ROUNDED\">{}</TABLE>>",
+        rows
+    )
+}
+
+/// Generate a same-rank band for each lane, with a label node on the side.
+/// Lanes appear in order of their first operation and are chained with invisible edges.
+fn generate_lane_stmts<O, A>(graph: &OpenHypergraph<O, A>, lane: &EdgeKey<A>) -> Vec<Stmt>
+where
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
+{
+    // Lane names in order of first appearance, and the edges assigned to each
+    let mut lanes: Vec<(String, Vec<usize>)> = Vec::new();
+    for (i, edge) in graph.hypergraph.edges.iter().enumerate() {
+        if let Some(name) = lane(edge) {
+            match lanes.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, members)) => members.push(i),
+                None => lanes.push((name, vec![i])),
+            }
+        }
+    }
+
+    let mut stmts = Vec::new();
+
+    for (k, (name, members)) in lanes.iter().enumerate() {
+        let mut lane_stmts = vec![
+            Stmt::Attribute(Attribute(
+                Id::Plain(String::from("rank")),
+                Id::Plain(String::from("same")),
+            )),
+            Stmt::Node(Node {
+                id: NodeId(Id::Plain(format!("lane_{}", k)), None),
+                attributes: vec![
+                    Attribute(
+                        Id::Plain(String::from("shape")),
+                        Id::Plain(String::from("plaintext")),
+                    ),
+                    Attribute(
+                        Id::Plain(String::from("label")),
+                        Id::Plain(format!("\"{}\"", escape_dot_label(name))),
+                    ),
+                ],
+            }),
+        ];
+        for i in members {
+            lane_stmts.push(Stmt::Node(Node {
+                id: NodeId(Id::Plain(format!("e_{}", i)), None),
+                attributes: vec![],
+            }));
+        }
+
+        stmts.push(Stmt::Subgraph(Subgraph {
+            id: Id::Plain(format!("lane_band_{}", k)),
+            stmts: lane_stmts,
+        }));
     }
 
-    // Add quotient connections (dotted lines between unified nodes)
-    let quotient_stmts = generate_quotient_stmts(graph);
-    for stmt in quotient_stmts {
-        dot_graph.add_stmt(stmt);
+    // Chain lane labels so the bands keep their order
+    for k in 1..lanes.len() {
+        stmts.push(Stmt::Edge(Edge {
+            ty: EdgeTy::Pair(
+                Vertex::N(NodeId(Id::Plain(format!("lane_{}", k - 1)), None)),
+                Vertex::N(NodeId(Id::Plain(format!("lane_{}", k)), None)),
+            ),
+            attributes: vec![Attribute(
+                Id::Plain(String::from("style")),
+                Id::Plain(String::from("invis")),
+            )],
+        }));
     }
 
-    dot_graph
+    stmts
 }
 
-// Unfortunately this seems to be a fundamental limitation of the dot syntax;
-// See https://forum.graphviz.org/t/how-do-i-properly-escape-arbitrary-text-for-use-in-labels/1762
-// > Unfortunately, due to past mistakes, we realized there is no way to safely put
-// > arbitrary text in graphviz strings, as we made mistakes in handling quotes and escapes.
-fn escape_dot_label(s: &str) -> String {
-    s.chars()
-        .flat_map(|c| match c {
-            '\\' => Some("\\\\".to_string()),
-            '"' => Some("\\\"".to_string()),
-            '{' => Some("\\{".to_string()),
-            '}' => Some("\\}".to_string()),
-            '|' => Some("\\|".to_string()),
-            '<' => Some("\\<".to_string()),
-            '>' => Some("\\>".to_string()),
-            _ => Some(c.to_string()),
-        })
-        .collect()
-}
+/// Generate a same-rank subgraph for each layer, in increasing layer order, with invisible edges
+/// from the first operation of each layer to the first of the next to enforce that order
+fn generate_layer_stmts<O, A>(graph: &OpenHypergraph<O, A>, layers: &EdgeLayer<A>) -> Vec<Stmt> {
+    let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (i, edge) in graph.hypergraph.edges.iter().enumerate() {
+        if let Some(layer) = layers(edge) {
+            members.entry(layer).or_default().push(i);
+        }
+    }
 
-/// Generate node statements for each node in the hypergraph
-fn generate_node_stmts<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> Vec<Stmt>
-where
-    O: Clone + Debug + PartialEq,
-    A: Clone + Debug + PartialEq,
-{
     let mut stmts = Vec::new();
 
-    for i in 0..graph.hypergraph.nodes.len() {
-        let label = (opts.node_label)(&graph.hypergraph.nodes[i]);
+    for (layer, members) in &members {
+        let mut layer_stmts = vec![Stmt::Attribute(Attribute(
+            Id::Plain(String::from("rank")),
+            Id::Plain(String::from("same")),
+        ))];
+        for i in members {
+            layer_stmts.push(Stmt::Node(Node {
+                id: NodeId(Id::Plain(format!("e_{}", i)), None),
+                attributes: vec![],
+            }));
+        }
 
-        // Escape special dot characters.
-        let label = escape_dot_label(&label);
+        stmts.push(Stmt::Subgraph(Subgraph {
+            id: Id::Plain(format!("layer_{}", layer)),
+            stmts: layer_stmts,
+        }));
+    }
 
-        stmts.push(Stmt::Node(Node {
-            id: NodeId(Id::Plain(format!("n_{}", i)), None),
-            attributes: vec![
-                Attribute(
-                    Id::Plain(String::from("shape")),
-                    Id::Plain(String::from("point")),
-                ),
-                Attribute(
-                    Id::Plain(String::from("xlabel")),
-                    Id::Plain(format!("\"{}\"", label)),
-                ),
-            ],
+    // Chain the representatives of consecutive layers
+    let representatives: Vec<usize> = members.values().map(|m| m[0]).collect();
+    for pair in representatives.windows(2) {
+        stmts.push(Stmt::Edge(Edge {
+            ty: EdgeTy::Pair(
+                Vertex::N(NodeId(Id::Plain(format!("e_{}", pair[0])), None)),
+                Vertex::N(NodeId(Id::Plain(format!("e_{}", pair[1])), None)),
+            ),
+            attributes: vec![Attribute(
+                Id::Plain(String::from("style")),
+                Id::Plain(String::from("invis")),
+            )],
         }));
     }
 
     stmts
 }
 
-/// Generate record node statements for each hyperedge
-fn generate_edge_stmts<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> Vec<Stmt>
-where
-    O: Clone + Debug + PartialEq,
-    A: Clone + Debug + PartialEq,
-{
-    let mut stmts = Vec::new();
+/// Attributes drawing a spider operation as a filled circle
+fn spider_attributes(color: &str) -> Vec<Attribute> {
+    vec![
+        Attribute(
+            Id::Plain(String::from("shape")),
+            Id::Plain(String::from("circle")),
+        ),
+        Attribute(
+            Id::Plain(String::from("style")),
+            Id::Plain(String::from("filled")),
+        ),
+        Attribute(
+            Id::Plain(String::from("fillcolor")),
+            Id::Plain(format!("\"{}\"", color)),
+        ),
+        Attribute(
+            Id::Plain(String::from("label")),
+            Id::Plain(String::from("\"\"")),
+        ),
+        Attribute(
+            Id::Plain(String::from("width")),
+            Id::Plain(String::from("0.2")),
+        ),
+    ]
+}
 
-    for i in 0..graph.hypergraph.edges.len() {
-        let hyperedge = &graph.hypergraph.adjacency[i];
-        let label = (opts.edge_label)(&graph.hypergraph.edges[i]);
-        let label = escape_dot_label(&label);
+/// Attributes drawing a copy operation as a small dot in the default node color
+fn copy_attributes() -> Vec<Attribute> {
+    vec![
+        Attribute(
+            Id::Plain(String::from("shape")),
+            Id::Plain(String::from("point")),
+        ),
+        Attribute(
+            Id::Plain(String::from("width")),
+            Id::Plain(String::from("0.08")),
+        ),
+    ]
+}
 
-        // Create port sections for sources
-        let mut source_ports = String::new();
-        for j in 0..hyperedge.sources.len() {
-            source_ports.push_str(&format!("<s_{j}> | "));
-        }
-        if !source_ports.is_empty() {
-            source_ports.truncate(source_ports.len() - 3); // Remove last " | "
-        }
+/// Compass points on the incoming and outgoing sides of the flow for an orientation
+fn flow_compass(orientation: Orientation) -> (&'static str, &'static str) {
+    match orientation {
+        Orientation::LR => ("w", "e"),
+        Orientation::TB => ("n", "s"),
+        Orientation::RL => ("e", "w"),
+        Orientation::BT => ("s", "n"),
+    }
+}
 
-        // Create port sections for targets
-        let mut target_ports = String::new();
-        for j in 0..hyperedge.targets.len() {
-            target_ports.push_str(&format!("<t_{j}> | "));
+/// A reference to the record port `name`, optionally pinned to a compass point
+fn record_port(name: String, compass: Option<&str>) -> Port {
+    match compass {
+        Some(compass) => Port(Some(Id::Plain(name)), Some(compass.to_string())),
+        None => Port(None, Some(name)),
+    }
+}
+
+/// Number of port cells drawn for an operation side with `count` ports
+fn shown_ports(count: usize, max_ports_shown: Option<usize>) -> usize {
+    max_ports_shown.map_or(count, |max| count.min(max))
+}
+
+/// Name of the record port that the `j`th of `count` ports attaches to; collapsed ports share
+/// the summary cell `<prefix>_more`
+fn port_name(prefix: &str, j: usize, count: usize, max_ports_shown: Option<usize>) -> String {
+    if j < shown_ports(count, max_ports_shown) {
+        format!("{}_{}", prefix, j)
+    } else {
+        format!("{}_more", prefix)
+    }
+}
+
+/// Generate a `{ rank=... }` subgraph for each kind of rank pin, holding the pinned operations
+fn generate_rank_pin_stmts<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    rank_pin: &EdgeRankPin<A>,
+) -> Vec<Stmt> {
+    let pins = [RankPin::Min, RankPin::Max, RankPin::Source, RankPin::Sink];
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); pins.len()];
+    for (i, edge) in graph.hypergraph.edges.iter().enumerate() {
+        if let Some(pin) = rank_pin(edge) {
+            let k = pins.iter().position(|p| *p == pin).unwrap();
+            members[k].push(i);
         }
-        if !target_ports.is_empty() {
-            target_ports.truncate(target_ports.len() - 3); // Remove last " | "
+    }
+
+    let mut stmts = Vec::new();
+    for (pin, members) in pins.iter().zip(members) {
+        if members.is_empty() {
+            continue;
         }
 
-        // Create full record label with proper quoting for GraphViz DOT format
-        let record_label = if source_ports.is_empty() && target_ports.is_empty() {
-            format!("\"{}\"", label)
-        } else if source_ports.is_empty() {
-            format!("\"{{ {} | {{ {} }} }}\"", label, target_ports)
-        } else if target_ports.is_empty() {
-            format!("\"{{ {{ {} }} | {} }}\"", source_ports, label)
-        } else {
-            format!(
-                "\"{{ {{ {} }} | {} | {{ {} }} }}\"",
-                source_ports, label, target_ports
-            )
-        };
+        let mut pin_stmts = vec![Stmt::Attribute(Attribute(
+            Id::Plain(String::from("rank")),
+            Id::Plain(pin.to_string()),
+        ))];
+        for i in members {
+            pin_stmts.push(Stmt::Node(Node {
+                id: NodeId(Id::Plain(format!("e_{}", i)), None),
+                attributes: vec![],
+            }));
+        }
 
-        stmts.push(Stmt::Node(Node {
-            id: NodeId(Id::Plain(format!("e_{}", i)), None),
-            attributes: vec![
-                Attribute(Id::Plain(String::from("label")), Id::Plain(record_label)),
-                Attribute(
-                    Id::Plain(String::from("shape")),
-                    Id::Plain(String::from("record")),
-                ),
-            ],
+        stmts.push(Stmt::Subgraph(Subgraph {
+            id: Id::Plain(format!("rank_pin_{}", pin)),
+            stmts: pin_stmts,
         }));
     }
 
     stmts
 }
 
-/// Generate statements connecting nodes to edges
-fn generate_connection_stmts<O, A>(graph: &OpenHypergraph<O, A>) -> Vec<Stmt>
+/// The node drawn for `node`: the representative of its unified nodes, or `node` itself when
+/// the index is out of range, so a dangling reference is drawn as a phantom node
+fn representative(rep: &[usize], node: usize) -> usize {
+    rep.get(node).copied().unwrap_or(node)
+}
+
+/// Generate statements connecting nodes to edges.
+///
+/// Every port gets its own connection, so a node appearing several times in an operation's
+/// sources (or targets) produces parallel connections from the same node to different ports.
+fn generate_connection_stmts<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+    rep: &[usize],
+    transposed: bool,
+) -> Vec<Stmt>
 where
-    O: Clone + Debug + PartialEq,
-    A: Clone + Debug + PartialEq,
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
 {
-    let mut stmts = Vec::new();
+    let num_connections = graph
+        .hypergraph
+        .adjacency
+        .iter()
+        .map(|h| h.sources.len() + h.targets.len())
+        .sum();
+    let mut stmts = Vec::with_capacity(num_connections);
+
+    // Source connections closing a feedback loop
+    let back_connections = if opts.detect_back_edges {
+        analysis::back_connections(graph)
+    } else {
+        HashSet::new()
+    };
+
+    // Compass points for source and target ports
+    let (source_compass, target_compass) = if opts.port_compass {
+        let (incoming, outgoing) = flow_compass(opts.orientation);
+        if transposed {
+            (Some(outgoing), Some(incoming))
+        } else {
+            (Some(incoming), Some(outgoing))
+        }
+    } else {
+        (None, None)
+    };
 
     for (i, hyperedge) in graph.hypergraph.adjacency.iter().enumerate() {
+        // Spiders, copies and compact operations have no ports: wires attach to the node itself
+        let edge = &graph.hypergraph.edges[i];
+        let is_dot = (opts.is_spider)(edge).is_some() || (opts.is_copy)(edge);
+        let has_ports = !is_dot && opts.operation_display == OperationDisplay::Ports;
+
         // Connections n_i → e_j:p_k
         for (j, &node_id) in hyperedge.sources.iter().enumerate() {
-            let node_idx = node_id.0; // Convert NodeId to usize
+            // Draw merged nodes as their representative
+            let node_idx = representative(rep, node_id.0);
 
             // Create a port with the correct format
-            let port = Some(Port(None, Some(format!("s_{}", j))));
+            let name = port_name("s", j, hyperedge.sources.len(), opts.max_ports_shown);
+            let port = has_ports.then(|| record_port(name, source_compass));
 
-            let edge = Edge {
-                ty: EdgeTy::Pair(
-                    Vertex::N(NodeId(Id::Plain(format!("n_{}", node_idx)), None)),
-                    Vertex::N(NodeId(Id::Plain(format!("e_{}", i)), port)),
-                ),
-                attributes: vec![],
+            let node = Vertex::N(NodeId(Id::Plain(format!("n_{}", node_idx)), None));
+            let operation = Vertex::N(NodeId(Id::Plain(format!("e_{}", i)), port));
+
+            // Back edges are drawn reversed, so they don't pull the operation's rank upwards
+            let is_back = back_connections.contains(&(i, j));
+            let mut edge = if is_back {
+                Edge {
+                    ty: EdgeTy::Pair(operation, node),
+                    attributes: vec![Attribute(
+                        Id::Plain(String::from("dir")),
+                        Id::Plain(String::from("back")),
+                    )],
+                }
+            } else {
+                Edge {
+                    ty: EdgeTy::Pair(node, operation),
+                    attributes: vec![],
+                }
             };
+            // Dangling wires have no label to color by
+            if let Some(color) = graph
+                .hypergraph
+                .nodes
+                .get(node_idx)
+                .and_then(|node| (opts.wire_color)(node))
+            {
+                edge.attributes.push(wire_color_attribute(color));
+            }
+            if opts.label_wires {
+                edge.attributes
+                    .push(wire_label_attribute(graph, opts, node_idx));
+            }
+            // Reversed back edges show the flow with their tail
+            if opts.connection_arrowhead != "none" {
+                let key = if is_back { "arrowtail" } else { "arrowhead" };
+                edge.attributes.push(Attribute(
+                    Id::Plain(String::from(key)),
+                    Id::Plain(format!("\"{}\"", opts.connection_arrowhead)),
+                ));
+            }
             stmts.push(Stmt::Edge(edge));
         }
 
         // Connect edge target ports to target nodes
         // Connections e_j:p_k → n_i
         for (j, &node_id) in hyperedge.targets.iter().enumerate() {
-            let node_idx = node_id.0; // Convert NodeId to usize
+            // Draw merged nodes as their representative
+            let node_idx = representative(rep, node_id.0);
 
             // Create a port with the correct format
-            let port = Some(Port(None, Some(format!("t_{}", j))));
+            let name = port_name("t", j, hyperedge.targets.len(), opts.max_ports_shown);
+            let port = has_ports.then(|| record_port(name, target_compass));
 
-            let edge = Edge {
+            let mut edge = Edge {
                 ty: EdgeTy::Pair(
                     Vertex::N(NodeId(Id::Plain(format!("e_{}", i)), port)),
                     Vertex::N(NodeId(Id::Plain(format!("n_{}", node_idx)), None)),
                 ),
                 attributes: vec![],
             };
+            // Dangling wires have no label to color by
+            if let Some(color) = graph
+                .hypergraph
+                .nodes
+                .get(node_idx)
+                .and_then(|node| (opts.wire_color)(node))
+            {
+                edge.attributes.push(wire_color_attribute(color));
+            }
+            if opts.label_wires {
+                edge.attributes
+                    .push(wire_label_attribute(graph, opts, node_idx));
+            }
+            if opts.connection_arrowhead != "none" {
+                edge.attributes.push(Attribute(
+                    Id::Plain(String::from("arrowhead")),
+                    Id::Plain(format!("\"{}\"", opts.connection_arrowhead)),
+                ));
+            }
             stmts.push(Stmt::Edge(edge));
         }
     }
 
+    // Reverse every connection for the transposed graph
+    if transposed {
+        for stmt in stmts.iter_mut() {
+            if let Stmt::Edge(Edge {
+                ty: EdgeTy::Pair(from, to),
+                ..
+            }) = stmt
+            {
+                std::mem::swap(from, to);
+            }
+        }
+    }
+
+    if opts.merge_duplicate_attachments {
+        merge_duplicate_attachments(&mut stmts);
+    }
+
     stmts
 }
 
-/// Generate interface nodes for sources and targets of the hypergraph
-fn generate_interface_stmts<O, A>(graph: &OpenHypergraph<O, A>) -> Vec<Stmt>
-where
-    O: Clone + Debug + PartialEq,
-    A: Clone + Debug + PartialEq,
-{
+/// The `label` attribute of a connection, showing the label of its wire node. Both ends of a wire
+/// use the same (representative) node, so their labels agree.
+fn wire_label_attribute<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+    node_idx: usize,
+) -> Attribute {
+    let label = if node_idx < graph.hypergraph.nodes.len() {
+        node_label_text(graph, opts, node_idx)
+    } else {
+        String::new()
+    };
+    Attribute(
+        Id::Plain(String::from("label")),
+        Id::Plain(format!("\"{}\"", (opts.escape)(&label))),
+    )
+}
+
+/// The quoted `color` attribute of a connection carrying a colored wire
+fn wire_color_attribute(color: String) -> Attribute {
+    Attribute(
+        Id::Plain(String::from("color")),
+        Id::Plain(format!("\"{}\"", color)),
+    )
+}
+
+/// Make parallel connections between one node and one operation meet at a single point on the
+/// node, by giving them a shared `sametail` (or `samehead`) group named after the operation
+fn merge_duplicate_attachments(stmts: &mut [Stmt]) {
+    // The node and operation ids of a connection, and whether the node is the tail
+    fn endpoints(stmt: &Stmt) -> Option<(String, String, bool)> {
+        match stmt {
+            Stmt::Edge(Edge {
+                ty:
+                    EdgeTy::Pair(
+                        Vertex::N(NodeId(Id::Plain(from), _)),
+                        Vertex::N(NodeId(Id::Plain(to), _)),
+                    ),
+                ..
+            }) => {
+                if from.starts_with("n_") {
+                    Some((from.clone(), to.clone(), true))
+                } else {
+                    Some((to.clone(), from.clone(), false))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    let mut counts: HashMap<(String, String, bool), usize> = HashMap::new();
+    for stmt in stmts.iter() {
+        if let Some(key) = endpoints(stmt) {
+            *counts.entry(key).or_default() += 1;
+        }
+    }
+
+    for stmt in stmts.iter_mut() {
+        if let Some((node, operation, node_is_tail)) = endpoints(stmt) {
+            if counts[&(node, operation.clone(), node_is_tail)] > 1 {
+                let group = if node_is_tail { "sametail" } else { "samehead" };
+                if let Stmt::Edge(edge) = stmt {
+                    edge.attributes.push(Attribute(
+                        Id::Plain(String::from(group)),
+                        Id::Plain(operation),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Color the nodes and operations on `path`, and the connections between consecutive steps
+fn highlight_path(stmts: &mut [Stmt], path: &[analysis::PathStep], color: &str) {
+    let ids: Vec<String> = path
+        .iter()
+        .map(|step| match step {
+            analysis::PathStep::Node(i) => format!("n_{}", i),
+            analysis::PathStep::Edge(i) => format!("e_{}", i),
+        })
+        .collect();
+    let links: HashSet<(&str, &str)> = ids
+        .windows(2)
+        .flat_map(|w| {
+            [
+                (w[0].as_str(), w[1].as_str()),
+                (w[1].as_str(), w[0].as_str()),
+            ]
+        })
+        .collect();
+
+    let highlight = vec![
+        (String::from("color"), format!("\"{}\"", color)),
+        (String::from("penwidth"), String::from("2")),
+    ];
+
+    for stmt in stmts {
+        match stmt {
+            Stmt::Node(node) => {
+                if let NodeId(Id::Plain(id), _) = &node.id {
+                    if ids.contains(id) {
+                        override_attributes(&mut node.attributes, highlight.clone());
+                    }
+                }
+            }
+            Stmt::Edge(Edge {
+                ty:
+                    EdgeTy::Pair(
+                        Vertex::N(NodeId(Id::Plain(from), _)),
+                        Vertex::N(NodeId(Id::Plain(to), _)),
+                    ),
+                attributes,
+            }) if links.contains(&(from.as_str(), to.as_str())) => {
+                override_attributes(attributes, highlight.clone());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Attributes drawing a highlighted operation in the accent color with a thicker outline
+fn highlight_attributes(theme: &Theme) -> Vec<(String, String)> {
+    vec![
+        (String::from("color"), format!("\"{}\"", theme.accent)),
+        (String::from("penwidth"), String::from("2")),
+    ]
+}
+
+/// Attributes drawing a highlighted node as a larger point in the accent color
+fn highlight_node_attributes(theme: &Theme) -> Vec<(String, String)> {
+    vec![
+        (String::from("color"), format!("\"{}\"", theme.accent)),
+        (String::from("width"), String::from("0.15")),
+    ]
+}
+
+/// Generate interface nodes for the given sources and targets of the hypergraph
+fn generate_interface_stmts<O, A>(
+    sources: &[lax::NodeId],
+    targets: &[lax::NodeId],
+    opts: &Options<O, A>,
+) -> Vec<Stmt> {
     let mut stmts = Vec::new();
 
+    // Compass points where connectors leave and enter, as (tailport, headport)
+    let (tail, head) = match &opts.interface_compass {
+        InterfaceCompass::Unset => (None, None),
+        InterfaceCompass::Auto => {
+            // Connectors run along the flow, like target connections
+            let (incoming, outgoing) = flow_compass(opts.orientation);
+            (Some(outgoing), Some(incoming))
+        }
+        InterfaceCompass::Custom { tail, head } => (Some(tail.as_str()), Some(head.as_str())),
+    };
+    // Compass suffix on a plain node; record ports use `record_port` instead
+    let node_compass = |compass: Option<&str>| compass.map(|c| Port(None, Some(c.to_string())));
+
+    // Interface boxes are hidden, or drawn with a title cell beside their ports
+    let interface_style = (!opts.visible_interfaces).then(|| {
+        Attribute(
+            Id::Plain(String::from("style")),
+            Id::Plain(String::from("invisible")),
+        )
+    });
+    let interface_title = |title: &str| {
+        if opts.visible_interfaces {
+            title.to_string()
+        } else {
+            String::from("{}")
+        }
+    };
+
+    // Interface boxes and connectors are tinted when the theme sets an interface color
+    let interface_color = opts.theme.interface_color.as_ref().map(|color| {
+        Attribute(
+            Id::Plain(String::from("color")),
+            Id::Plain(format!("\"{}\"", color)),
+        )
+    });
+
+    // Connectors are dashed, except those of highlighted pass-through nodes
+    let connector_attributes = |node: usize| {
+        let is_passthrough =
+            sources.iter().any(|n| n.0 == node) && targets.iter().any(|n| n.0 == node);
+        if opts.highlight_passthrough && is_passthrough {
+            vec![
+                Attribute(
+                    Id::Plain(String::from("color")),
+                    Id::Plain(format!("\"{}\"", opts.theme.accent.clone())),
+                ),
+                Attribute(
+                    Id::Plain(String::from("penwidth")),
+                    Id::Plain(String::from("2")),
+                ),
+                // A heavy weight keeps the wire short and straight
+                Attribute(
+                    Id::Plain(String::from("weight")),
+                    Id::Plain(String::from("10")),
+                ),
+            ]
+        } else {
+            let mut attributes = vec![Attribute(
+                Id::Plain(String::from("style")),
+                Id::Plain(String::from("dashed")),
+            )];
+            attributes.extend(interface_color.clone());
+            attributes
+        }
+    };
+
     // Create source interface record node
-    if !graph.sources.is_empty() {
+    if !sources.is_empty() {
         // Create port sections for sources
         let mut source_ports = String::new();
-        for i in 0..graph.sources.len() {
+        for i in 0..sources.len() {
             source_ports.push_str(&format!("<p_{i}> | "));
         }
         // Remove last " | "
@@ -295,52 +2478,58 @@ where
         }
 
         // Create the source interface node
-        stmts.push(Stmt::Node(Node {
+        let mut attributes = vec![
+            Attribute(
+                Id::Plain(String::from("label")),
+                Id::Plain(format!(
+                    "\"{{ {} | {{ {} }} }}\"",
+                    interface_title("inputs"),
+                    source_ports
+                )),
+            ),
+            Attribute(
+                Id::Plain(String::from("shape")),
+                Id::Plain(String::from("record")),
+            ),
+        ];
+        attributes.extend(interface_style.clone());
+        attributes.extend(interface_color.clone());
+        let interface_node = Stmt::Node(Node {
             id: NodeId(Id::Plain(String::from("sources")), None),
-            attributes: vec![
-                Attribute(
-                    Id::Plain(String::from("label")),
-                    Id::Plain(format!("\"{{ {{}} | {{ {} }} }}\"", source_ports)),
-                ),
-                Attribute(
-                    Id::Plain(String::from("shape")),
-                    Id::Plain(String::from("record")),
-                ),
-                Attribute(
-                    Id::Plain(String::from("style")),
-                    Id::Plain(String::from("invisible")),
-                ),
-                Attribute(
-                    Id::Plain(String::from("rank")),
-                    Id::Plain(String::from("source")),
-                ),
-            ],
-        }));
+            attributes,
+        });
+        stmts.extend(interface_rank_stmts(
+            "sources",
+            "source",
+            interface_node,
+            sources,
+            targets,
+        ));
 
         // Connect source interface ports to the source nodes
-        for (i, &source_node_id) in graph.sources.iter().enumerate() {
+        for (i, &source_node_id) in sources.iter().enumerate() {
             let edge = Edge {
                 ty: EdgeTy::Pair(
                     Vertex::N(NodeId(
                         Id::Plain(String::from("sources")),
-                        Some(Port(None, Some(format!("p_{}", i)))),
+                        Some(record_port(format!("p_{}", i), tail)),
+                    )),
+                    Vertex::N(NodeId(
+                        Id::Plain(format!("n_{}", source_node_id.0)),
+                        node_compass(head),
                     )),
-                    Vertex::N(NodeId(Id::Plain(format!("n_{}", source_node_id.0)), None)),
                 ),
-                attributes: vec![Attribute(
-                    Id::Plain(String::from("style")),
-                    Id::Plain(String::from("dashed")),
-                )],
+                attributes: connector_attributes(source_node_id.0),
             };
             stmts.push(Stmt::Edge(edge));
         }
     }
 
     // Create target interface record node
-    if !graph.targets.is_empty() {
+    if !targets.is_empty() {
         // Create port sections for targets
         let mut target_ports = String::new();
-        for i in 0..graph.targets.len() {
+        for i in 0..targets.len() {
             target_ports.push_str(&format!("<p_{i}> | "));
         }
         // Remove last " | "
@@ -349,42 +2538,48 @@ where
         }
 
         // Create the target interface node
-        stmts.push(Stmt::Node(Node {
+        let mut attributes = vec![
+            Attribute(
+                Id::Plain(String::from("label")),
+                Id::Plain(format!(
+                    "\"{{ {{ {} }} | {} }}\"",
+                    target_ports,
+                    interface_title("outputs")
+                )),
+            ),
+            Attribute(
+                Id::Plain(String::from("shape")),
+                Id::Plain(String::from("record")),
+            ),
+        ];
+        attributes.extend(interface_style.clone());
+        attributes.extend(interface_color.clone());
+        let interface_node = Stmt::Node(Node {
             id: NodeId(Id::Plain(String::from("targets")), None),
-            attributes: vec![
-                Attribute(
-                    Id::Plain(String::from("label")),
-                    Id::Plain(format!("\"{{ {{ {} }} | {{}} }}\"", target_ports)),
-                ),
-                Attribute(
-                    Id::Plain(String::from("shape")),
-                    Id::Plain(String::from("record")),
-                ),
-                Attribute(
-                    Id::Plain(String::from("style")),
-                    Id::Plain(String::from("invisible")),
-                ),
-                Attribute(
-                    Id::Plain(String::from("rank")),
-                    Id::Plain(String::from("sink")),
-                ),
-            ],
-        }));
+            attributes,
+        });
+        stmts.extend(interface_rank_stmts(
+            "targets",
+            "sink",
+            interface_node,
+            targets,
+            sources,
+        ));
 
         // Connect target nodes to target interface ports
-        for (i, &target_node_id) in graph.targets.iter().enumerate() {
+        for (i, &target_node_id) in targets.iter().enumerate() {
             let edge = Edge {
                 ty: EdgeTy::Pair(
-                    Vertex::N(NodeId(Id::Plain(format!("n_{}", target_node_id.0)), None)),
+                    Vertex::N(NodeId(
+                        Id::Plain(format!("n_{}", target_node_id.0)),
+                        node_compass(tail),
+                    )),
                     Vertex::N(NodeId(
                         Id::Plain(String::from("targets")),
-                        Some(Port(None, Some(format!("p_{}", i)))),
+                        Some(record_port(format!("p_{}", i), head)),
                     )),
                 ),
-                attributes: vec![Attribute(
-                    Id::Plain(String::from("style")),
-                    Id::Plain(String::from("dashed")),
-                )],
+                attributes: connector_attributes(target_node_id.0),
             };
             stmts.push(Stmt::Edge(edge));
         }
@@ -393,52 +2588,97 @@ where
     stmts
 }
 
-/// Generate statements for quotient connections (dotted lines between unified nodes)
-fn generate_quotient_stmts<O, A>(graph: &OpenHypergraph<O, A>) -> Vec<Stmt>
+/// Place an interface node in a `rank` subgraph (`source` or `sink`), which pins it to the first
+/// or last rank, and align its wires on a single rank next to it. GraphViz ignores `rank` on a
+/// node, so both need subgraphs. Pass-through wires, which also belong to `other`, are left out so
+/// the two interfaces are not pulled onto the same rank.
+fn interface_rank_stmts(
+    name: &str,
+    rank: &str,
+    interface_node: Stmt,
+    wires: &[lax::NodeId],
+    other: &[lax::NodeId],
+) -> Vec<Stmt> {
+    let mut stmts = vec![Stmt::Subgraph(Subgraph {
+        id: Id::Plain(format!("rank_{}", name)),
+        stmts: vec![
+            Stmt::Attribute(Attribute(
+                Id::Plain(String::from("rank")),
+                Id::Plain(rank.to_string()),
+            )),
+            interface_node,
+        ],
+    })];
+
+    let mut wire_stmts = vec![Stmt::Attribute(Attribute(
+        Id::Plain(String::from("rank")),
+        Id::Plain(String::from("same")),
+    ))];
+    let mut seen = HashSet::new();
+    for wire in wires {
+        if other.contains(wire) || !seen.insert(wire.0) {
+            continue;
+        }
+        wire_stmts.push(Stmt::Node(Node {
+            id: NodeId(Id::Plain(format!("n_{}", wire.0)), None),
+            attributes: vec![],
+        }));
+    }
+    if wire_stmts.len() > 1 {
+        stmts.push(Stmt::Subgraph(Subgraph {
+            id: Id::Plain(format!("rank_{}_wires", name)),
+            stmts: wire_stmts,
+        }));
+    }
+
+    stmts
+}
+
+/// Generate statements for quotient connections (by default dotted lines between unified nodes)
+fn generate_quotient_stmts<O, A>(graph: &OpenHypergraph<O, A>, style: &QuotientStyle) -> Vec<Stmt>
 where
-    O: Clone + Debug + PartialEq,
-    A: Clone + Debug + PartialEq,
+    O: Clone + Debug + PartialEq + MaybeSync,
+    A: Clone + Debug + PartialEq + MaybeSync,
 {
     let mut stmts = Vec::new();
 
-    // Extract unified node pairs from the quotient
-    let (lefts, rights) = &graph.hypergraph.quotient;
-
-    // Create a map to track which nodes are unified
-    let mut unified_nodes = std::collections::HashMap::new();
-
-    for (left, right) in lefts.iter().zip(rights.iter()) {
-        let left_idx = left.0; // Access the internal usize
-        let right_idx = right.0;
+    for (left_idx, right_idx) in quotient_pairs(graph) {
+        // Create an undirected edge between unified nodes
+        let mut attributes = vec![
+            Attribute(
+                Id::Plain(String::from("style")),
+                Id::Plain(String::from("dotted")),
+            ),
+            Attribute(
+                Id::Plain(String::from("dir")),
+                Id::Plain(String::from("none")),
+            ),
+        ];
+        override_attributes(&mut attributes, style.to_pairs());
 
-        // Check if we've already seen this pair (in any order)
-        let pair_key = if left_idx < right_idx {
-            (left_idx, right_idx)
-        } else {
-            (right_idx, left_idx)
+        let edge = Edge {
+            ty: EdgeTy::Pair(
+                Vertex::N(NodeId(Id::Plain(format!("n_{}", left_idx)), None)),
+                Vertex::N(NodeId(Id::Plain(format!("n_{}", right_idx)), None)),
+            ),
+            attributes,
         };
-
-        if unified_nodes.insert(pair_key, true).is_none() {
-            // Create a dashed edge between unified nodes
-            let edge = Edge {
-                ty: EdgeTy::Pair(
-                    Vertex::N(NodeId(Id::Plain(format!("n_{}", left_idx)), None)),
-                    Vertex::N(NodeId(Id::Plain(format!("n_{}", right_idx)), None)),
-                ),
-                attributes: vec![
-                    Attribute(
-                        Id::Plain(String::from("style")),
-                        Id::Plain(String::from("dotted")),
-                    ),
-                    Attribute(
-                        Id::Plain(String::from("dir")),
-                        Id::Plain(String::from("none")),
-                    ),
-                ],
-            };
-            stmts.push(Stmt::Edge(edge));
-        }
+        stmts.push(Stmt::Edge(edge));
     }
 
     stmts
 }
+
+/// The quotient pairs drawn as edges: a node unified with itself would be drawn as a self-loop
+/// and is skipped, and each pair is kept once, in the order of its first occurrence, whichever
+/// way round it is unified
+fn quotient_pairs<O, A>(graph: &OpenHypergraph<O, A>) -> Vec<(usize, usize)> {
+    let (lefts, rights) = &graph.hypergraph.quotient;
+    let mut seen = HashSet::new();
+    lefts
+        .iter()
+        .zip(rights.iter())
+        .map(|(left, right)| (left.0, right.0))
+        .filter(|&(left, right)| left != right && seen.insert((left.min(right), left.max(right))))
+        .collect()
+}
@@ -0,0 +1,113 @@
+//! Mermaid flowchart output, for documentation platforms without GraphViz
+use crate::{edge_label_text, node_label_text, Options, Orientation};
+use open_hypergraphs::lax::OpenHypergraph;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::fmt::Write;
+
+/// Generates a Mermaid `flowchart` representation of a lax open hypergraph.
+///
+/// This is a basic translation: nodes are drawn as small unlabelled circles, operations as
+/// subroutine shapes, connections as arrows, and the interfaces as `inputs`/`outputs` nodes.
+/// Like the `xlabel` of a GraphViz wire, the node label is shown on each arrow touching the node.
+/// Quotient pairs become dotted links. Labels honor the labelling and numbering options, and
+/// styling options specific to GraphViz are ignored.
+pub fn generate_mermaid<O, A>(graph: &OpenHypergraph<O, A>, opts: &Options<O, A>) -> String
+where
+    O: Clone + Debug + PartialEq,
+    A: Clone + Debug + PartialEq,
+{
+    let mut out = String::new();
+
+    // Mermaid calls top-to-bottom `TD`
+    let direction = match opts.orientation {
+        Orientation::LR => "LR",
+        Orientation::TB => "TD",
+        Orientation::RL => "RL",
+        Orientation::BT => "BT",
+    };
+    writeln!(out, "flowchart {}", direction).unwrap();
+
+    // Nodes, with their labels carried by the connections
+    let num_nodes = graph.hypergraph.nodes.len();
+    for i in 0..num_nodes {
+        writeln!(out, "    n_{}@{{ shape: sm-circ }}", i).unwrap();
+    }
+    let arrow = |node: usize| {
+        let label = if node < num_nodes {
+            node_label_text(graph, opts, node)
+        } else {
+            String::new()
+        };
+        if label.is_empty() {
+            String::from("-->")
+        } else {
+            format!("-->|\"{}\"|", escape_mermaid_label(&label))
+        }
+    };
+
+    // Operations
+    for i in 0..graph.hypergraph.edges.len() {
+        let edge = &graph.hypergraph.edges[i];
+        if (opts.is_spider)(edge).is_some() || (opts.is_copy)(edge) {
+            writeln!(out, "    e_{}((\" \"))", i).unwrap();
+            continue;
+        }
+
+        let label = edge_label_text(graph, opts, i);
+        writeln!(out, "    e_{}[[\"{}\"]]", i, escape_mermaid_label(&label)).unwrap();
+    }
+
+    // Connections, in port order
+    for (i, hyperedge) in graph.hypergraph.adjacency.iter().enumerate() {
+        for node in &hyperedge.sources {
+            writeln!(out, "    n_{} {} e_{}", node.0, arrow(node.0), i).unwrap();
+        }
+        for node in &hyperedge.targets {
+            writeln!(out, "    e_{} {} n_{}", i, arrow(node.0), node.0).unwrap();
+        }
+    }
+
+    // Interfaces
+    if opts.show_interfaces && !graph.sources.is_empty() {
+        writeln!(out, "    inputs([\"inputs\"])").unwrap();
+        for node in &graph.sources {
+            writeln!(out, "    inputs {} n_{}", arrow(node.0), node.0).unwrap();
+        }
+    }
+    if opts.show_interfaces && !graph.targets.is_empty() {
+        writeln!(out, "    outputs([\"outputs\"])").unwrap();
+        for node in &graph.targets {
+            writeln!(out, "    n_{} {} outputs", node.0, arrow(node.0)).unwrap();
+        }
+    }
+
+    // Quotient pairs, deduplicated and without self-loops like the DOT output
+    let mut seen = HashSet::new();
+    let (lefts, rights) = &graph.hypergraph.quotient;
+    for (left, right) in lefts.iter().zip(rights.iter()) {
+        let pair_key = (left.0.min(right.0), left.0.max(right.0));
+        if left.0 != right.0 && seen.insert(pair_key) {
+            writeln!(out, "    n_{} -.- n_{}", left.0, right.0).unwrap();
+        }
+    }
+
+    out
+}
+
+/// Escape a label for use inside a quoted Mermaid string, using Mermaid's entity codes
+fn escape_mermaid_label(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '"' => "#quot;".to_string(),
+            '#' => "#35;".to_string(),
+            '<' => "#lt;".to_string(),
+            '>' => "#gt;".to_string(),
+            // Mermaid renders HTML line breaks in labels
+            '\n' => "<br/>".to_string(),
+            '\t' => "    ".to_string(),
+            '\r' => "".to_string(),
+            _ => c.to_string(),
+        })
+        .collect()
+}
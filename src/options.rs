@@ -1,11 +1,277 @@
+use crate::color::normalize_color;
+use crate::escape_dot_label;
+use dot_structures::Stmt;
+use open_hypergraphs::lax::OpenHypergraph;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
+
+/// Boxed callback types. With the `rayon` feature callbacks must be `Send + Sync`, so statements
+/// can be generated in parallel.
+#[cfg(not(feature = "rayon"))]
+macro_rules! callback {
+    ($($signature:tt)*) => { Box<dyn Fn $($signature)*> };
+}
+
+#[cfg(feature = "rayon")]
+macro_rules! callback {
+    ($($signature:tt)*) => { Box<dyn Fn $($signature)* + Send + Sync> };
+}
+
+/// Bound on labels and labellers which is `Send + Sync` with the `rayon` feature, and is
+/// implemented by every type otherwise
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Send + Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T: Send + Sync + ?Sized> MaybeSync for T {}
+
+/// Bound on labels and labellers which is `Send + Sync` with the `rayon` feature, and is
+/// implemented by every type otherwise
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// A predicate on node labels
+pub type NodePredicate<O> = callback!((&O) -> bool);
+
+/// A predicate on operation labels
+pub type EdgePredicate<A> = callback!((&A) -> bool);
+
+/// Assigns an operation to an optional named group
+pub type EdgeKey<A> = callback!((&A) -> Option<String>);
+
+/// Optionally computes a color for an operation
+pub type EdgeColor<A> = callback!((&A) -> Option<String>);
+
+/// Computes a label from the whole graph and the index of a node or edge
+pub type GraphLabel<O, A> = callback!((&OpenHypergraph<O, A>, usize) -> String);
+
+/// Computes extra statements for an element, given its index and label
+pub type ExtraStmts<T> = callback!((usize, &T) -> Vec<Stmt>);
+
+/// Optionally assigns a color to a node
+pub type NodeColor<O> = callback!((&O) -> Option<String>);
+
+/// Computes extra attributes for a node or operation from its label
+pub type ExtraAttributes<T> = callback!((&T) -> Vec<(String, String)>);
+
+/// Optionally assigns an operation to a rank layer
+pub type EdgeLayer<A> = callback!((&A) -> Option<usize>);
+
+/// Optionally pins an operation to an extreme rank
+pub type EdgeRankPin<A> = callback!((&A) -> Option<RankPin>);
+
+/// Escapes label text for use inside a quoted DOT string
+pub type LabelEscape = callback!((&str) -> String);
 
 pub struct Options<O, A> {
     pub orientation: Orientation,
     pub theme: Theme,
-    pub node_label: Box<dyn Fn(&O) -> String>,
-    pub edge_label: Box<dyn Fn(&A) -> String>,
+    /// Labels each node. Closures `Fn(&O) -> String` implement [`NodeLabeller`], but need
+    /// their argument type annotated, e.g. `Box::new(|n: &Bit| n.to_string())`.
+    pub node_label: Box<dyn NodeLabeller<O>>,
+    /// Labels each operation. Closures `Fn(&A) -> String` implement [`EdgeLabeller`], but need
+    /// their argument type annotated, e.g. `Box::new(|e: &Gate| e.to_string())`.
+    pub edge_label: Box<dyn EdgeLabeller<A>>,
+    /// Marks nodes holding state (e.g. registers), which are drawn with `state_node_shape`
+    pub node_is_state: Option<NodePredicate<O>>,
+    /// GraphViz shape used for state nodes
+    pub state_node_shape: String,
+    /// Wrap the whole diagram in a bordered frame with this title
+    pub frame: Option<String>,
+    /// Whether the interface nodes are drawn inside the frame
+    pub frame_interfaces: bool,
+    /// Margin around nodes when removing overlaps (`sep` graph attribute)
+    pub sep: Option<Separation>,
+    /// Margin around nodes for edge routing (`esep` graph attribute)
+    pub esep: Option<Separation>,
+    /// Minimum distance between ranks in inches (`ranksep` graph attribute)
+    pub ranksep: Option<f64>,
+    /// Minimum distance between nodes in the same rank in inches (`nodesep` graph attribute)
+    pub nodesep: Option<f64>,
+    /// Assigns operations to named lanes, drawn as labelled same-rank bands across the flow
+    pub lane: Option<EdgeKey<A>>,
+    /// Computes the visual style of each operation; unset fields inherit the defaults
+    pub edge_style: callback!((&A) -> OperationStyle),
+    /// Computes the visual style of each wire node; unset fields inherit the defaults
+    pub node_style: callback!((&O) -> WireStyle),
+    /// Draw a faint background grid with this spacing (in inches).
+    ///
+    /// GraphViz has no native grid, so this is approximated with an xdot `_background` drawing.
+    /// The layout size isn't known before GraphViz runs, so the grid covers a square estimated
+    /// from the number of nodes and operations, and is clipped to the final canvas.
+    pub background_grid: Option<f64>,
+    /// Maximum number of port cells drawn per side of an operation. Remaining ports are collapsed
+    /// into a single "+k more" cell, which their connections attach to.
+    pub max_ports_shown: Option<usize>,
+    /// Node overlap removal strategy (`overlap` graph attribute)
+    pub overlap: Option<Overlap>,
+    /// Draw connections which feed back to an earlier operation reversed (with `dir=back`), so
+    /// GraphViz routes them along the side instead of through the layout. Acyclic graphs are
+    /// unaffected.
+    pub detect_back_edges: bool,
+    /// Split the output into `structure` and `labels` layers which SVG viewers can toggle.
+    ///
+    /// GraphViz assigns layers to whole nodes and edges, not to individual attributes: wire nodes
+    /// carrying an `xlabel` go on the `labels` layer (connections still meet where the point was),
+    /// while operation records keep their text on the `structure` layer. Layer support in SVG
+    /// viewers varies; most show every layer unless the `<g>` groups are toggled with CSS or
+    /// script.
+    pub use_layers: bool,
+    /// Marks operations as spiders (e.g. for ZX-calculus diagrams) by returning a fill color.
+    /// Spiders are drawn as filled circles with their wires attached to the circle itself
+    /// rather than to directional record ports.
+    pub is_spider: EdgeColor<A>,
+    /// Style applied to nodes in the source interface (`graph.sources`)
+    pub source_node_style: Option<WireStyle>,
+    /// Style applied to nodes in the target interface (`graph.targets`)
+    pub target_node_style: Option<WireStyle>,
+    /// Context-aware node labels, given the graph and node index. Takes precedence over
+    /// `node_label` when set.
+    pub node_label_graph: Option<GraphLabel<O, A>>,
+    /// Context-aware operation labels, given the graph and edge index. Takes precedence over
+    /// `edge_label` when set.
+    pub edge_label_graph: Option<GraphLabel<O, A>>,
+    /// Attach wires to a fixed side of operation ports, following the orientation: sources enter
+    /// on the incoming side (west for `LR`, north for `TB`) and targets leave on the opposite one.
+    pub port_compass: bool,
+    /// Also draw a tiny unlabelled copy of the structure in a separate cluster, as an overview
+    /// for pan/zoom viewers. GraphViz can't pin clusters to a corner, so the layout engine
+    /// decides where it sits, usually beside the main diagram.
+    pub minimap: bool,
+    /// Substituted for empty operation labels, so the label cell doesn't collapse (e.g. `"·"`)
+    pub empty_label_placeholder: Option<String>,
+    /// Compass sides where interface connectors leave and enter. These are emitted as port
+    /// suffixes on the connector endpoints (equivalent to `tailport`/`headport`), so the
+    /// interface record ports are kept.
+    pub interface_compass: InterfaceCompass,
+    /// Highlight pass-through nodes, which appear in both the source and target interface, by
+    /// drawing their interface connectors as one solid, straight wire in the theme accent color
+    pub highlight_passthrough: bool,
+    /// Extra attributes for specific wire nodes, keyed by node index. Values are quoted, and
+    /// override generated attributes with the same name. Out-of-range indices are ignored.
+    pub extra_node_attributes: HashMap<usize, Vec<(String, String)>>,
+    /// Extra attributes for specific operations, keyed by edge index. Values are quoted, and
+    /// override generated attributes with the same name. Out-of-range indices are ignored.
+    pub extra_edge_attributes: HashMap<usize, Vec<(String, String)>>,
+    /// How operations are drawn: with a cell per port, or as a compact box showing their arity
+    pub operation_display: OperationDisplay,
+    /// How the port cells of record operations are arranged
+    pub record_layout: RecordLayout,
+    /// Extra statements appended after each wire node's statement, e.g. annotation nodes.
+    ///
+    /// Generated ids are `n_<i>`, `e_<i>`, `sources` and `targets`; callers are responsible for
+    /// choosing ids for new nodes which don't collide with these or with each other.
+    pub extra_node_stmts: Option<ExtraStmts<O>>,
+    /// Extra statements appended after each operation's statement, e.g. a note beside it.
+    ///
+    /// Generated ids are `n_<i>`, `e_<i>`, `sources` and `targets`; callers are responsible for
+    /// choosing ids for new nodes which don't collide with these or with each other.
+    pub extra_edge_stmts: Option<ExtraStmts<A>>,
+    /// When a node is attached to several ports of the same operation, make those parallel
+    /// connections leave the node from one point (`sametail`/`samehead`, `dot` engine only)
+    pub merge_duplicate_attachments: bool,
+    /// Pins individual operations to the first or last rank
+    pub rank_pin: Option<EdgeRankPin<A>>,
+    /// Omit unnecessary quotes, and print without whitespace using [`crate::print_dot`]
+    pub minify: bool,
+    /// Highlight a shortest path from the first node index to the second, if one exists
+    pub highlight_path: Option<(usize, usize)>,
+    /// Prefix each operation's label with its edge index, e.g. `[3] And`
+    pub number_operations: bool,
+    /// Prefix each node's label with its node index, e.g. `[0] Bool`
+    pub number_nodes: bool,
+    /// Assigns operations to explicit rank layers, drawn in increasing order.
+    /// Unrelated to `use_layers`, which uses GraphViz's layer feature.
+    pub layers: Option<EdgeLayer<A>>,
+    /// Fill color for each operation's record, or `None` to leave it unfilled
+    pub edge_color: EdgeColor<A>,
+    /// Draw operations with HTML-like table labels instead of record strings.
+    /// Only affects `OperationDisplay::Ports`.
+    pub html_labels: bool,
+    /// Name of the top-level graph, quoted in the output if necessary
+    pub graph_id: String,
+    /// Emit a `strict digraph`, so GraphViz merges duplicate edges
+    pub strict: bool,
+    /// Extra graph attributes such as `("splines", "ortho")`, emitted after the built-in ones.
+    /// Values are quoted.
+    pub graph_attributes: Vec<(String, String)>,
+    /// Extra attributes for each node, overriding the defaults. Values are quoted.
+    pub node_attributes: ExtraAttributes<O>,
+    /// Extra attributes for each operation, e.g. `tooltip` or `href`. Values are quoted.
+    pub edge_attributes: ExtraAttributes<A>,
+    /// Color of the connections to and from each node, or `None` for the theme default
+    pub wire_color: NodeColor<O>,
+    /// Draw a legend of `(label, color)` entries, e.g. those used by `edge_color`. Skipped if empty.
+    pub legend: Option<Vec<(String, String)>>,
+    /// Draw the `sources` and `targets` interface nodes and their connectors
+    pub show_interfaces: bool,
+    /// Appearance of the edges between unified nodes
+    pub quotient_style: QuotientStyle,
+    /// Whether unified nodes are joined by edges or merged into one node
+    pub quotient_mode: QuotientMode,
+    /// Label each connection with the label of its wire node, as configured by `node_label`,
+    /// `node_label_graph` and `number_nodes`
+    pub label_wires: bool,
+    /// Show the label of each connected wire node in the port cells of operations, matching
+    /// `label_wires`
+    pub label_ports: bool,
+    /// Marks copying operations (e.g. the `Copy` operations created by the `var` interface),
+    /// which are drawn as a small dot with all their wires attached to it
+    pub is_copy: EdgePredicate<A>,
+    /// Groups operations into bordered clusters labelled by their key; operations with key
+    /// `None` are not clustered
+    pub cluster_key: EdgeKey<A>,
+    /// Indices of operations, spiders and copies included, to draw in the theme's accent color
+    /// with a thicker outline
+    pub highlight_edges: HashSet<usize>,
+    /// Indices of nodes to draw as larger points in the theme's accent color
+    pub highlight_nodes: HashSet<usize>,
+    /// Start the output of [`crate::print_dot_with`] with a `//` comment giving the crate version
+    /// and the node and edge counts. Only `print_dot_with` reads this: the other printing and
+    /// rendering functions take an already generated graph, which no longer has the counts.
+    pub comment_header: bool,
+    /// Prepended to every generated identifier (nodes, operations, interfaces, subgraphs and the
+    /// graph id), so the output of several graphs can be combined without collisions
+    pub id_prefix: String,
+    /// Break node and operation labels longer than this many characters into lines, at word
+    /// boundaries
+    pub label_max_width: Option<usize>,
+    /// Where the labels of wire nodes are drawn
+    pub node_label_placement: LabelPlacement,
+    /// Arrowhead drawn on each connection, e.g. `normal` or `vee` to show the direction of flow.
+    /// Emitted on connections only when it differs from the default edge attribute `none`.
+    pub connection_arrowhead: String,
+    /// Merge parallel edges into shared lines (`concentrate` graph attribute)
+    pub concentrate: bool,
+    /// How connections are routed (`splines` graph attribute), or `None` for GraphViz's default
+    pub splines: Option<Splines>,
+    /// Emit a directed graph with `->` edges. When `false`, the graph is undirected, with `--`
+    /// edges and no arrows, which suits hypergraphs representing symmetric relations.
+    pub directed: bool,
+    /// Escapes node, operation and port labels before they are quoted, defaulting to
+    /// [`escape_dot_label`]. HTML labels (see `html_labels`) are always escaped as HTML.
+    pub escape: LabelEscape,
+    /// Shape of record operations, `record` or the rounded `Mrecord`
+    pub edge_shape: RecordShape,
+    /// Draw the interface boxes, titled `inputs` and `outputs`, instead of hiding them
+    pub visible_interfaces: bool,
+    /// Sort the generated statements into a canonical order, so small changes to a graph give
+    /// small diffs of its DOT output. Within the graph and each subgraph, statements are ordered
+    /// as follows, comparing ids with their numbers by value (`n_2` before `n_10`):
+    ///
+    /// 1. graph and default attributes, in generation order
+    /// 2. wire nodes (`n_*`), by id
+    /// 3. operation nodes (`e_*`), by id
+    /// 4. other nodes, such as annotations, by id
+    /// 5. subgraphs, by id, each sorted in the same way
+    /// 6. edges, by tail id and port, then head id and port
+    ///
+    /// Statements which compare equal keep their generation order.
+    pub canonical_order: bool,
 }
 
 impl<O: Debug, A: Debug> Default for Options<O, A> {
@@ -13,8 +279,215 @@ impl<O: Debug, A: Debug> Default for Options<O, A> {
         Self {
             orientation: Default::default(),
             theme: Default::default(),
-            node_label: Box::new(|n| format!("{:?}", n)),
-            edge_label: Box::new(|e| format!("{:?}", e)),
+            node_label: Box::new(|n: &O| format!("{:?}", n)),
+            edge_label: Box::new(|e: &A| format!("{:?}", e)),
+            node_is_state: None,
+            state_node_shape: String::from("square"),
+            frame: None,
+            frame_interfaces: true,
+            sep: None,
+            esep: None,
+            ranksep: None,
+            nodesep: None,
+            lane: None,
+            edge_style: Box::new(|_| OperationStyle::default()),
+            node_style: Box::new(|_| WireStyle::default()),
+            background_grid: None,
+            max_ports_shown: None,
+            overlap: None,
+            detect_back_edges: false,
+            use_layers: false,
+            is_spider: Box::new(|_| None),
+            source_node_style: None,
+            target_node_style: None,
+            node_label_graph: None,
+            edge_label_graph: None,
+            port_compass: false,
+            minimap: false,
+            empty_label_placeholder: None,
+            interface_compass: InterfaceCompass::default(),
+            highlight_passthrough: false,
+            extra_node_attributes: HashMap::new(),
+            extra_edge_attributes: HashMap::new(),
+            operation_display: OperationDisplay::default(),
+            record_layout: RecordLayout::default(),
+            extra_node_stmts: None,
+            extra_edge_stmts: None,
+            merge_duplicate_attachments: false,
+            rank_pin: None,
+            minify: false,
+            highlight_path: None,
+            number_operations: false,
+            number_nodes: false,
+            layers: None,
+            edge_color: Box::new(|_| None),
+            html_labels: false,
+            graph_id: String::from("G"),
+            strict: false,
+            graph_attributes: Vec::new(),
+            node_attributes: Box::new(|_| Vec::new()),
+            edge_attributes: Box::new(|_| Vec::new()),
+            wire_color: Box::new(|_| None),
+            legend: None,
+            show_interfaces: true,
+            quotient_style: QuotientStyle::default(),
+            quotient_mode: QuotientMode::default(),
+            label_wires: false,
+            label_ports: false,
+            is_copy: Box::new(|_| false),
+            cluster_key: Box::new(|_| None),
+            highlight_edges: HashSet::new(),
+            highlight_nodes: HashSet::new(),
+            comment_header: false,
+            id_prefix: String::new(),
+            label_max_width: None,
+            node_label_placement: LabelPlacement::default(),
+            connection_arrowhead: String::from("none"),
+            concentrate: false,
+            splines: None,
+            directed: true,
+            escape: Box::new(escape_dot_label),
+            edge_shape: RecordShape::default(),
+            visible_interfaces: false,
+            canonical_order: false,
+        }
+    }
+}
+
+impl<O: Debug + 'static, A: Debug + 'static> Options<O, A> {
+    /// Default options with node and edge labels formatted using `style`
+    pub fn with_label_style(style: LabelStyle) -> Self {
+        Self {
+            node_label: Box::new(move |n: &O| style.format(n)),
+            edge_label: Box::new(move |e: &A| style.format(e)),
+            ..Default::default()
+        }
+    }
+}
+
+impl<O: Debug + Display + 'static, A: Debug + Display + 'static> Options<O, A> {
+    /// Default options with node and edge labels formatted using `Display` instead of `Debug`
+    pub fn with_display() -> Self {
+        Self {
+            node_label: Box::new(|n: &O| format!("{}", n)),
+            edge_label: Box::new(|e: &A| format!("{}", e)),
+            ..Default::default()
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Builder
+
+/// Fluent construction of [`Options`], starting from `Options::default()`
+pub struct OptionsBuilder<O, A> {
+    options: Options<O, A>,
+}
+
+impl<O: Debug, A: Debug> Options<O, A> {
+    /// Start building options from the defaults
+    pub fn builder() -> OptionsBuilder<O, A> {
+        OptionsBuilder::default()
+    }
+}
+
+impl<O: Debug, A: Debug> Default for OptionsBuilder<O, A> {
+    fn default() -> Self {
+        Self {
+            options: Options::default(),
+        }
+    }
+}
+
+impl<O, A> OptionsBuilder<O, A> {
+    /// Set the layout direction
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.options.orientation = orientation;
+        self
+    }
+
+    /// Set the color theme
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.options.theme = theme;
+        self
+    }
+
+    /// Set the function labelling nodes
+    pub fn node_label(mut self, node_label: impl Fn(&O) -> String + MaybeSync + 'static) -> Self {
+        self.options.node_label = Box::new(node_label);
+        self
+    }
+
+    /// Set a named type labelling nodes
+    pub fn node_labeller(mut self, node_labeller: impl NodeLabeller<O> + 'static) -> Self {
+        self.options.node_label = Box::new(node_labeller);
+        self
+    }
+
+    /// Set the function labelling operations
+    pub fn edge_label(mut self, edge_label: impl Fn(&A) -> String + MaybeSync + 'static) -> Self {
+        self.options.edge_label = Box::new(edge_label);
+        self
+    }
+
+    /// Set a named type labelling operations
+    pub fn edge_labeller(mut self, edge_labeller: impl EdgeLabeller<A> + 'static) -> Self {
+        self.options.edge_label = Box::new(edge_labeller);
+        self
+    }
+
+    /// Finish building
+    pub fn build(self) -> Options<O, A> {
+        self.options
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Labels
+
+/// Produces the label of a node. Implemented for closures, and for any named type (which, unlike
+/// a closure, can be `Clone` so one configuration can be reused across graphs).
+pub trait NodeLabeller<O>: MaybeSync {
+    fn label(&self, node: &O) -> String;
+}
+
+impl<O, F: Fn(&O) -> String + MaybeSync> NodeLabeller<O> for F {
+    fn label(&self, node: &O) -> String {
+        self(node)
+    }
+}
+
+/// Produces the label of an operation. Implemented for closures, and for any named type.
+pub trait EdgeLabeller<A>: MaybeSync {
+    fn label(&self, edge: &A) -> String;
+}
+
+impl<A, F: Fn(&A) -> String + MaybeSync> EdgeLabeller<A> for F {
+    fn label(&self, edge: &A) -> String {
+        self(edge)
+    }
+}
+
+/// How default labels are derived from a value's `Debug` representation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LabelStyle {
+    /// The full `Debug` output, e.g. `Mul(Config { width: 8 })`
+    #[default]
+    Debug,
+    /// Only the constructor name: the `Debug` output up to the first `(` or `{`, e.g. `Mul`
+    VariantOnly,
+}
+
+impl LabelStyle {
+    /// Format a value according to this style
+    pub fn format<T: Debug>(&self, value: &T) -> String {
+        let label = format!("{:?}", value);
+        match self {
+            LabelStyle::Debug => label,
+            LabelStyle::VariantOnly => match label.find(['(', '{']) {
+                Some(end) => label[..end].trim_end().to_string(),
+                None => label,
+            },
         }
     }
 }
@@ -22,14 +495,20 @@ impl<O: Debug, A: Debug> Default for Options<O, A> {
 ////////////////////////////////////////////////////////////////////////////////
 // Orientation
 
-/// Graph orientation for visualization
+/// Graph orientation for visualization. With the `serde` feature it is (de)serialized as the
+/// variant name, e.g. `"LR"`.
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Orientation {
     /// Left to right layout
     LR,
     /// Top to bottom layout
     #[default]
     TB,
+    /// Right to left layout
+    RL,
+    /// Bottom to top layout
+    BT,
 }
 
 // Used for dot output
@@ -38,6 +517,341 @@ impl fmt::Display for Orientation {
         match self {
             Orientation::LR => write!(f, "LR"),
             Orientation::TB => write!(f, "TB"),
+            Orientation::RL => write!(f, "RL"),
+            Orientation::BT => write!(f, "BT"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Operation display
+
+/// How operation nodes are drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OperationDisplay {
+    /// A record with a cell for each source and target port
+    #[default]
+    Ports,
+    /// A box labelled with the operation and its arity, e.g. `Mul (2→1)`; wires attach to the
+    /// box directly
+    CompactArity,
+}
+
+/// How the sections of a record operation (source ports, label, target ports) are arranged.
+///
+/// GraphViz flips the direction of record fields at each level of `{}` nesting, so this chooses
+/// between wrapping the sections in braces or not. The arrangements below are for `TB`; they are
+/// transposed for `LR`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RecordLayout {
+    /// Sources above the label above targets, with the ports of each spread side by side
+    #[default]
+    Stacked,
+    /// Sources, label and targets side by side, with the ports of each stacked vertically
+    Spread,
+}
+
+/// Where the label of a wire node is drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LabelPlacement {
+    /// The node is a `shape=point` dot with the label floating beside it as an `xlabel`, which
+    /// may overlap neighbouring nodes and connections
+    #[default]
+    Xlabel,
+    /// The node is drawn as its label (`shape=plaintext`) with no dot, and wires meet at the
+    /// edges of the text. Nodes with an empty label remain points, and state nodes keep their
+    /// marker and `xlabel`.
+    Inline,
+}
+
+/// The GraphViz shape of record operations. Both share the same port syntax.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RecordShape {
+    /// Boxes with square corners (`record`)
+    #[default]
+    Record,
+    /// Boxes with rounded corners (`Mrecord`)
+    Mrecord,
+}
+
+impl fmt::Display for RecordShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordShape::Record => write!(f, "record"),
+            RecordShape::Mrecord => write!(f, "Mrecord"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Quotient mode
+
+/// How nodes unified by the quotient are drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuotientMode {
+    /// Each node is drawn, with an edge between each unified pair
+    #[default]
+    Edges,
+    /// Each class of unified nodes is drawn as a single node `n_<k>`, where `k` is the smallest
+    /// index in the class, and every connection is attached to it
+    Merge,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Interface compass
+
+/// Compass sides used by the dashed connectors between interface records and boundary nodes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InterfaceCompass {
+    /// Let GraphViz choose where connectors attach
+    #[default]
+    Unset,
+    /// Leave along the flow and enter from the opposite side (`e`/`w` for `LR`, `s`/`n` for `TB`)
+    Auto,
+    /// Explicit `tailport` and `headport` compass points, e.g. `"s"` and `"n"`
+    Custom { tail: String, head: String },
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Styles
+
+/// Visual attributes of an operation's record node. Fields left as `None` inherit the defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperationStyle {
+    pub color: Option<String>,
+    pub fillcolor: Option<String>,
+    pub fontcolor: Option<String>,
+    pub fontsize: Option<f64>,
+    pub penwidth: Option<f64>,
+    /// Overrides the `record` shape; only `record` and `Mrecord` keep port cells
+    pub shape: Option<String>,
+}
+
+impl OperationStyle {
+    /// GraphViz attribute pairs for the fields that are set
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        if let Some(color) = &self.color {
+            pairs.push((String::from("color"), format!("\"{}\"", color)));
+        }
+        if let Some(fillcolor) = &self.fillcolor {
+            // Operations are rounded by default, so keep that when filling
+            pairs.push((String::from("style"), String::from("\"rounded,filled\"")));
+            pairs.push((String::from("fillcolor"), format!("\"{}\"", fillcolor)));
+        }
+        if let Some(fontcolor) = &self.fontcolor {
+            pairs.push((String::from("fontcolor"), format!("\"{}\"", fontcolor)));
+        }
+        if let Some(fontsize) = self.fontsize {
+            pairs.push((String::from("fontsize"), fontsize.to_string()));
+        }
+        if let Some(penwidth) = self.penwidth {
+            pairs.push((String::from("penwidth"), penwidth.to_string()));
+        }
+        if let Some(shape) = &self.shape {
+            pairs.push((String::from("shape"), shape.clone()));
+        }
+        pairs
+    }
+}
+
+/// Visual attributes of a wire node. Fields left as `None` inherit the defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WireStyle {
+    pub color: Option<String>,
+    pub fontcolor: Option<String>,
+    pub fontsize: Option<f64>,
+    /// Diameter of the point, in inches
+    pub width: Option<f64>,
+    /// Overrides the `point` shape
+    pub shape: Option<String>,
+}
+
+impl WireStyle {
+    /// GraphViz attribute pairs for the fields that are set
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        if let Some(color) = &self.color {
+            pairs.push((String::from("color"), format!("\"{}\"", color)));
+        }
+        if let Some(fontcolor) = &self.fontcolor {
+            pairs.push((String::from("fontcolor"), format!("\"{}\"", fontcolor)));
+        }
+        if let Some(fontsize) = self.fontsize {
+            pairs.push((String::from("fontsize"), fontsize.to_string()));
+        }
+        if let Some(width) = self.width {
+            pairs.push((String::from("width"), width.to_string()));
+        }
+        if let Some(shape) = &self.shape {
+            pairs.push((String::from("shape"), shape.clone()));
+        }
+        pairs
+    }
+}
+
+/// Visual attributes of the edges between unified (quotiented) nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotientStyle {
+    /// Color of the line, or `None` for the theme default
+    pub color: Option<String>,
+    /// GraphViz line style, e.g. `dotted`, `dashed` or `solid`, or several separated by commas
+    pub style: String,
+    pub penwidth: Option<f64>,
+}
+
+impl Default for QuotientStyle {
+    fn default() -> Self {
+        Self {
+            color: None,
+            style: String::from("dotted"),
+            penwidth: None,
+        }
+    }
+}
+
+impl QuotientStyle {
+    /// GraphViz attribute pairs for the style and the fields that are set
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        // Quoted, since a style may combine several, e.g. `dashed,bold`
+        let mut pairs = vec![(String::from("style"), format!("\"{}\"", self.style))];
+        if let Some(color) = &self.color {
+            pairs.push((String::from("color"), format!("\"{}\"", color)));
+        }
+        if let Some(penwidth) = self.penwidth {
+            pairs.push((String::from("penwidth"), penwidth.to_string()));
+        }
+        pairs
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Separation
+
+/// A node margin for the `sep` and `esep` graph attributes.
+///
+/// GraphViz accepts two forms: a bare number, which scales node sizes by `1 + value`, and a number
+/// prefixed with `+`, which pads nodes by that many points. Both are only honoured by the
+/// force-directed engines (`neato`, `fdp`, ...); `dot` ignores them. When both are set, `esep`
+/// should be strictly smaller than `sep`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Separation {
+    /// Scale node sizes by `1 + value` (emitted as `"value"`)
+    Scale(f64),
+    /// Pad nodes by a fixed number of points (emitted as `"+value"`)
+    Add(f64),
+}
+
+impl Separation {
+    /// The attribute value, or `None` if the margin is negative or not finite
+    pub fn to_dot(&self) -> Option<String> {
+        match *self {
+            Separation::Scale(x) if x.is_finite() && x >= 0.0 => Some(format!("{}", x)),
+            Separation::Add(x) if x.is_finite() && x >= 0.0 => Some(format!("+{}", x)),
+            _ => None,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Rank pins
+
+/// A rank constraint for a single operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RankPin {
+    /// On the minimum rank, possibly shared with other nodes
+    Min,
+    /// On the maximum rank, possibly shared with other nodes
+    Max,
+    /// Alone (with other `Source` pins) on the minimum rank
+    Source,
+    /// Alone (with other `Sink` pins) on the maximum rank
+    Sink,
+}
+
+// Used for dot output
+impl fmt::Display for RankPin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RankPin::Min => write!(f, "min"),
+            RankPin::Max => write!(f, "max"),
+            RankPin::Source => write!(f, "source"),
+            RankPin::Sink => write!(f, "sink"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Overlap
+
+/// Node overlap removal strategy.
+///
+/// Only the force-directed engines (`neato`, `fdp`, `sfdp`, ...) use this; `dot` never produces
+/// overlapping nodes and ignores it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overlap {
+    /// Leave overlaps in place (GraphViz's default)
+    Retain,
+    /// Remove overlaps with the default algorithm (`prism` in recent GraphViz versions)
+    Remove,
+    /// Remove overlaps with the Prism algorithm
+    Prism,
+    /// Scale the layout uniformly until nodes don't overlap
+    Scale,
+    /// Scale x and y separately until nodes don't overlap
+    ScaleXY,
+    /// Shrink the layout as far as possible without introducing overlaps
+    Compress,
+    /// Remove overlaps with variable placement constraints
+    Vpsc,
+    /// Remove overlaps while preserving orthogonal ordering
+    Ortho,
+}
+
+// Used for dot output
+impl fmt::Display for Overlap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Overlap::Retain => write!(f, "true"),
+            Overlap::Remove => write!(f, "false"),
+            Overlap::Prism => write!(f, "prism"),
+            Overlap::Scale => write!(f, "scale"),
+            Overlap::ScaleXY => write!(f, "scalexy"),
+            Overlap::Compress => write!(f, "compress"),
+            Overlap::Vpsc => write!(f, "vpsc"),
+            Overlap::Ortho => write!(f, "ortho"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Splines
+
+/// Edge routing mode.
+///
+/// `Ortho` doesn't support ports: connections attach to the center of operation records rather
+/// than to their port cells, so the order of sources and targets is lost. GraphViz also places
+/// edge labels poorly with `Ortho`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Splines {
+    /// Straight line segments
+    Line,
+    /// Curved arcs
+    Curved,
+    /// Axis-aligned segments at right angles
+    Ortho,
+    /// Straight segments bending to avoid nodes
+    Polyline,
+}
+
+// Used for dot output
+impl fmt::Display for Splines {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Splines::Line => write!(f, "line"),
+            Splines::Curved => write!(f, "curved"),
+            Splines::Ortho => write!(f, "ortho"),
+            Splines::Polyline => write!(f, "polyline"),
         }
     }
 }
@@ -45,12 +859,34 @@ impl fmt::Display for Orientation {
 ////////////////////////////////////////////////////////////////////////////////
 // Themes
 
-/// Theme for graph visualization
+/// Theme for graph visualization.
+///
+/// With the `serde` feature a theme is (de)serialized as a map with the same field names, and
+/// missing fields take their values from [`Theme::default`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Theme {
     pub bgcolor: String,
     pub fontcolor: String,
     pub color: String,
+    /// Color used for decorations such as frame borders
+    pub accent: String,
+    /// Preferred layout direction of the preset. Generation always uses [`Options::orientation`],
+    /// which takes precedence over this field, including for deserialized themes.
     pub orientation: Orientation,
+    /// Output resolution in dots per inch
+    pub dpi: Option<u32>,
+    /// Maximum output size in inches, as `(width, height)`
+    pub size: Option<(f64, f64)>,
+    /// Font family for node and edge labels
+    pub fontname: Option<String>,
+    /// Font size in points for node and edge labels
+    pub fontsize: Option<f64>,
+    /// Line thickness in points for nodes and edges
+    pub penwidth: Option<f64>,
+    /// Color of the interface boxes and their connectors, or `None` to use `color`
+    pub interface_color: Option<String>,
 }
 
 pub fn light_theme() -> Theme {
@@ -58,7 +894,14 @@ pub fn light_theme() -> Theme {
         bgcolor: String::from("white"),
         fontcolor: String::from("black"),
         color: String::from("black"),
+        accent: String::from("#1f6feb"),
         orientation: Orientation::LR,
+        dpi: None,
+        size: None,
+        fontname: None,
+        fontsize: None,
+        penwidth: None,
+        interface_color: None,
     }
 }
 /// A dark theme preset
@@ -67,7 +910,48 @@ pub fn dark_theme() -> Theme {
         bgcolor: String::from("#4a4a4a"),
         fontcolor: String::from("white"),
         color: String::from("white"),
+        accent: String::from("#f0c674"),
+        orientation: Orientation::LR,
+        dpi: None,
+        size: None,
+        fontname: None,
+        fontsize: None,
+        penwidth: None,
+        interface_color: None,
+    }
+}
+
+/// A Solarized light theme preset: `base3` background, with `base01` text and `base00` lines
+pub fn solarized_light() -> Theme {
+    Theme {
+        bgcolor: String::from("#fdf6e3"),
+        fontcolor: String::from("#586e75"),
+        color: String::from("#657b83"),
+        accent: String::from("#268bd2"),
+        orientation: Orientation::LR,
+        dpi: None,
+        size: None,
+        fontname: None,
+        fontsize: None,
+        penwidth: None,
+        interface_color: None,
+    }
+}
+
+/// A Solarized dark theme preset: `base03` background, with `base1` text and `base0` lines
+pub fn solarized_dark() -> Theme {
+    Theme {
+        bgcolor: String::from("#002b36"),
+        fontcolor: String::from("#93a1a1"),
+        color: String::from("#839496"),
+        accent: String::from("#b58900"),
         orientation: Orientation::LR,
+        dpi: None,
+        size: None,
+        fontname: None,
+        fontsize: None,
+        penwidth: None,
+        interface_color: None,
     }
 }
 
@@ -76,3 +960,120 @@ impl Default for Theme {
         dark_theme()
     }
 }
+
+/// An invalid color in a [`Theme`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeError {
+    /// `field` is neither a known GraphViz color name nor a `#rgb`, `#rrggbb` or `#rrggbbaa` hex
+    /// string
+    InvalidColor { field: &'static str, value: String },
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::InvalidColor { field, value } => {
+                write!(f, "invalid color for {}: {:?}", field, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+/// A partial theme: fields set to `Some` replace the corresponding field of a base theme
+#[derive(Debug, Clone, Default)]
+pub struct ThemeOverrides {
+    pub bgcolor: Option<String>,
+    pub fontcolor: Option<String>,
+    pub color: Option<String>,
+    pub accent: Option<String>,
+    pub orientation: Option<Orientation>,
+    pub dpi: Option<u32>,
+    pub size: Option<(f64, f64)>,
+    pub fontname: Option<String>,
+    pub fontsize: Option<f64>,
+    pub penwidth: Option<f64>,
+    pub interface_color: Option<String>,
+}
+
+impl Theme {
+    /// A light theme preset with a transparent background, for compositing over other content.
+    /// The background is emitted as `bgcolor="transparent"`, which GraphViz reads as the keyword
+    /// (as it does `"none"`).
+    pub fn transparent() -> Theme {
+        Theme {
+            bgcolor: String::from("transparent"),
+            ..light_theme()
+        }
+    }
+
+    /// Create a theme from colors, checking that each is a GraphViz color name or a hex string.
+    /// Short hex strings `#rgb` are expanded to `#rrggbb`, which GraphViz requires.
+    pub fn try_new(
+        bgcolor: &str,
+        fontcolor: &str,
+        color: &str,
+        accent: &str,
+        orientation: Orientation,
+    ) -> Result<Theme, ThemeError> {
+        let check = |field: &'static str, value: &str| {
+            normalize_color(value).ok_or_else(|| ThemeError::InvalidColor {
+                field,
+                value: value.to_string(),
+            })
+        };
+        Ok(Theme {
+            bgcolor: check("bgcolor", bgcolor)?,
+            fontcolor: check("fontcolor", fontcolor)?,
+            color: check("color", color)?,
+            accent: check("accent", accent)?,
+            orientation,
+            dpi: None,
+            size: None,
+            fontname: None,
+            fontsize: None,
+            penwidth: None,
+            interface_color: None,
+        })
+    }
+
+    /// Check the colors of an existing theme, e.g. one loaded from a configuration file
+    pub fn validate(&self) -> Result<(), ThemeError> {
+        Theme::try_new(
+            &self.bgcolor,
+            &self.fontcolor,
+            &self.color,
+            &self.accent,
+            self.orientation,
+        )?;
+        match &self.interface_color {
+            Some(value) if normalize_color(value).is_none() => Err(ThemeError::InvalidColor {
+                field: "interface_color",
+                value: value.clone(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Layer `overrides` on top of `base`, keeping base fields which aren't overridden
+    pub fn with_overrides(base: &Theme, overrides: ThemeOverrides) -> Theme {
+        Theme {
+            bgcolor: overrides.bgcolor.unwrap_or_else(|| base.bgcolor.clone()),
+            fontcolor: overrides
+                .fontcolor
+                .unwrap_or_else(|| base.fontcolor.clone()),
+            color: overrides.color.unwrap_or_else(|| base.color.clone()),
+            accent: overrides.accent.unwrap_or_else(|| base.accent.clone()),
+            orientation: overrides.orientation.unwrap_or(base.orientation),
+            dpi: overrides.dpi.or(base.dpi),
+            size: overrides.size.or(base.size),
+            fontname: overrides.fontname.or_else(|| base.fontname.clone()),
+            fontsize: overrides.fontsize.or(base.fontsize),
+            penwidth: overrides.penwidth.or(base.penwidth),
+            interface_color: overrides
+                .interface_color
+                .or_else(|| base.interface_color.clone()),
+        }
+    }
+}
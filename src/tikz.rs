@@ -0,0 +1,178 @@
+//! TikZ output, for including diagrams in LaTeX documents
+use crate::{analysis, edge_label_text, node_label_text, Options};
+use open_hypergraphs::lax::OpenHypergraph;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Spacing of the naive layered layout used by [`generate_tikz`], in centimetres
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TikzOptions {
+    /// Horizontal distance between a column of operations and the column of wires it produces
+    pub layer_sep: f64,
+    /// Vertical distance between consecutive nodes in a column
+    pub node_sep: f64,
+}
+
+impl Default for TikzOptions {
+    fn default() -> Self {
+        Self {
+            layer_sep: 1.5,
+            node_sep: 1.0,
+        }
+    }
+}
+
+/// Generates a TikZ `tikzpicture` of a lax open hypergraph.
+///
+/// The layout is naive: every operation is placed in the column after the latest operation
+/// producing one of its sources, and each wire in the column after its producer (wires without a
+/// producer come first). Nodes in a column are stacked top to bottom in index order. Unified nodes
+/// are drawn as a single wire, and the interfaces as dashed stubs at the left and right edges.
+/// Dangling node references are drawn as bare coordinates, so their connections still show.
+/// Labels honor the labelling and numbering options; all other options are ignored.
+pub fn generate_tikz<O, A>(
+    graph: &OpenHypergraph<O, A>,
+    opts: &Options<O, A>,
+    tikz: &TikzOptions,
+) -> String {
+    let adjacency = &graph.hypergraph.adjacency;
+
+    // Dangling node indices represent themselves, and are drawn as bare coordinates
+    let mut rep = analysis::quotient_representatives(graph);
+    let num_nodes = rep.len();
+    let dangling: HashSet<usize> = adjacency
+        .iter()
+        .flat_map(|hyperedge| hyperedge.sources.iter().chain(&hyperedge.targets))
+        .chain(graph.sources.iter().chain(&graph.targets))
+        .map(|node| node.0)
+        .filter(|&k| k >= num_nodes)
+        .collect();
+    let referenced = dangling.iter().map(|&k| k + 1).max().unwrap_or(num_nodes);
+    rep.extend(num_nodes..referenced);
+    let is_wire = |k: usize| rep[k] == k && (k < num_nodes || dangling.contains(&k));
+
+    // Operation producing each wire (indexed by representative)
+    let mut producer = vec![None; rep.len()];
+    for (i, hyperedge) in adjacency.iter().enumerate() {
+        for node in &hyperedge.targets {
+            producer[rep[node.0]] = Some(i);
+        }
+    }
+
+    // Columns of operations: relax until stable, bounded so cycles terminate
+    let mut op_layer = vec![1; adjacency.len()];
+    for _ in 0..adjacency.len() {
+        let mut changed = false;
+        for (i, hyperedge) in adjacency.iter().enumerate() {
+            let layer = hyperedge
+                .sources
+                .iter()
+                .filter_map(|node| producer[rep[node.0]])
+                .map(|p| op_layer[p] + 1)
+                .max()
+                .unwrap_or(1);
+            if layer > op_layer[i] {
+                op_layer[i] = layer;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Columns of wires, interleaved with those of operations
+    let wire_column = |k: usize| producer[k].map_or(1, |p| 2 * op_layer[p] + 1);
+    let op_column = |i: usize| 2 * op_layer[i];
+    let last_column = (0..rep.len())
+        .filter(|&k| is_wire(k))
+        .map(wire_column)
+        .chain((0..adjacency.len()).map(op_column))
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    // Stack the elements of each column
+    let mut heights = vec![0; last_column + 1];
+    let mut place = |column: usize| {
+        let row = heights[column];
+        heights[column] += 1;
+        (
+            column as f64 * tikz.layer_sep,
+            // Subtract, rather than negate, so the first row isn't printed as -0.00
+            0.0 - row as f64 * tikz.node_sep,
+        )
+    };
+
+    let mut out = String::new();
+    writeln!(out, "\\begin{{tikzpicture}}").unwrap();
+
+    // Wires
+    for k in (0..rep.len()).filter(|&k| is_wire(k)) {
+        let (x, y) = place(wire_column(k));
+        if k >= num_nodes {
+            writeln!(out, "  \\coordinate (n{}) at ({:.2}, {:.2});", k, x, y).unwrap();
+            continue;
+        }
+        let label = escape_tikz_label(&node_label_text(graph, opts, k));
+        writeln!(
+            out,
+            "  \\node[circle, fill, inner sep=1pt, label=above:{{{}}}] (n{}) at ({:.2}, {:.2}) {{}};",
+            label, k, x, y
+        )
+        .unwrap();
+    }
+
+    // Operations
+    for i in 0..adjacency.len() {
+        let (x, y) = place(op_column(i));
+        let label = escape_tikz_label(&edge_label_text(graph, opts, i));
+        writeln!(
+            out,
+            "  \\node[draw, rounded corners] (e{}) at ({:.2}, {:.2}) {{{}}};",
+            i, x, y, label
+        )
+        .unwrap();
+    }
+
+    // Connections, entering operations on the left and leaving on the right
+    for (i, hyperedge) in adjacency.iter().enumerate() {
+        for node in &hyperedge.sources {
+            writeln!(out, "  \\draw (n{}) -- (e{}.west);", rep[node.0], i).unwrap();
+        }
+        for node in &hyperedge.targets {
+            writeln!(out, "  \\draw (e{}.east) -- (n{});", i, rep[node.0]).unwrap();
+        }
+    }
+
+    // Interfaces
+    for (k, node) in graph.sources.iter().enumerate() {
+        let (x, y) = place(0);
+        writeln!(out, "  \\coordinate (s{}) at ({:.2}, {:.2});", k, x, y).unwrap();
+        writeln!(out, "  \\draw[dashed] (s{}) -- (n{});", k, rep[node.0]).unwrap();
+    }
+    for (k, node) in graph.targets.iter().enumerate() {
+        let (x, y) = place(last_column);
+        writeln!(out, "  \\coordinate (t{}) at ({:.2}, {:.2});", k, x, y).unwrap();
+        writeln!(out, "  \\draw[dashed] (n{}) -- (t{});", rep[node.0], k).unwrap();
+    }
+
+    writeln!(out, "\\end{{tikzpicture}}").unwrap();
+    out
+}
+
+/// Escape LaTeX special characters in a label
+fn escape_tikz_label(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\\' => "\\textbackslash{}".to_string(),
+            '{' | '}' | '#' | '$' | '%' | '&' | '_' => format!("\\{}", c),
+            '~' => "\\textasciitilde{}".to_string(),
+            '^' => "\\textasciicircum{}".to_string(),
+            '\n' => "\\\\".to_string(),
+            '\t' => " ".to_string(),
+            '\r' => "".to_string(),
+            _ => c.to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::*;
+use open_hypergraphs::lax::Hyperedge;
+use open_hypergraphs_dot::Options;
+
+/// `F: (x, y) → z` and `G: z → y`, feeding `G`'s output back into `F`
+fn feedback() -> Term {
+    let mut graph = Term::empty();
+    let [x, y, z] = ["A"; 3].map(|label| graph.new_node(label));
+    graph.new_edge(
+        "F",
+        Hyperedge {
+            sources: vec![x, y],
+            targets: vec![z],
+        },
+    );
+    graph.new_edge(
+        "G",
+        Hyperedge {
+            sources: vec![z],
+            targets: vec![y],
+        },
+    );
+    graph.sources = vec![x];
+    graph.targets = vec![z];
+    graph
+}
+
+fn detecting() -> Options<&'static str, &'static str> {
+    Options {
+        detect_back_edges: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn feedback_connections_are_reversed() {
+    let dot = render(&feedback(), &detecting());
+    assert_eq!(lines_with(&dot, "e_0:s_1"), ["e_0:s_1 -> n_1 [dir=back]"]);
+    // Forward connections are unchanged
+    for connection in [
+        "n_0 -> e_0:s_0",
+        "e_0:t_0 -> n_2",
+        "n_2 -> e_1:s_0",
+        "e_1:t_0 -> n_1",
+    ] {
+        assert_eq!(lines_with(&dot, connection), [connection]);
+    }
+
+    let dot = render(&feedback(), &Options::default());
+    assert_eq!(lines_with(&dot, "e_0:s_1"), ["n_1 -> e_0:s_1"]);
+}
+
+#[test]
+fn acyclic_graphs_are_unchanged() {
+    assert_eq!(
+        render(&copy_mul(), &detecting()),
+        render(&copy_mul(), &Options::default())
+    );
+}
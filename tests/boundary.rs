@@ -0,0 +1,251 @@
+mod common;
+
+use common::*;
+use open_hypergraphs::lax::Hyperedge;
+use open_hypergraphs_dot::{generate_dot_with, Options, Theme, WireStyle};
+
+/// `x ↦ x * x`, squaring by reading the same wire into both inputs of one `Mul`
+fn square() -> Term {
+    let mut graph = Term::empty();
+    let x = graph.new_node("A");
+    let y = graph.new_node("A");
+    graph.new_edge(
+        "Mul",
+        Hyperedge {
+            sources: vec![x, x],
+            targets: vec![y],
+        },
+    );
+    graph.sources = vec![x];
+    graph.targets = vec![y];
+    graph
+}
+
+/// A `Dup` operation writing the same wire to both of its outputs
+fn dup() -> Term {
+    let mut graph = Term::empty();
+    let x = graph.new_node("A");
+    let y = graph.new_node("A");
+    graph.new_edge(
+        "Dup",
+        Hyperedge {
+            sources: vec![x],
+            targets: vec![y, y],
+        },
+    );
+    graph.sources = vec![x];
+    graph.targets = vec![y];
+    graph
+}
+
+#[test]
+fn duplicate_sources_get_a_connection_per_port() {
+    let dot = render(&square(), &Options::default());
+    let connections = lines_with(&dot, "n_0 -> e_0");
+    assert_eq!(connections, ["n_0 -> e_0:s_0", "n_0 -> e_0:s_1"]);
+}
+
+#[test]
+fn duplicate_targets_get_a_connection_per_port() {
+    let dot = render(&dup(), &Options::default());
+    let connections = lines_with(&dot, "e_0:t_");
+    assert_eq!(connections, ["e_0:t_0 -> n_1", "e_0:t_1 -> n_1"]);
+}
+
+#[test]
+fn merged_duplicate_attachments_share_an_end() {
+    let opts = Options {
+        merge_duplicate_attachments: true,
+        ..Default::default()
+    };
+
+    let dot = render(&square(), &opts);
+    let connections = lines_with(&dot, "n_0 -> e_0");
+    assert_eq!(
+        connections,
+        [
+            "n_0 -> e_0:s_0 [sametail=e_0]",
+            "n_0 -> e_0:s_1 [sametail=e_0]"
+        ]
+    );
+    // A node attached only once is left alone
+    assert_eq!(lines_with(&dot, "e_0:t_0"), ["e_0:t_0 -> n_1"]);
+
+    let dot = render(&dup(), &opts);
+    let connections = lines_with(&dot, "e_0:t_");
+    assert_eq!(
+        connections,
+        [
+            "e_0:t_0 -> n_1 [samehead=e_0]",
+            "e_0:t_1 -> n_1 [samehead=e_0]"
+        ]
+    );
+}
+
+#[test]
+fn duplicate_interface_nodes_get_a_port_each() {
+    let mut graph = square();
+    graph.sources = vec![graph.sources[0], graph.sources[0]];
+    let dot = render(&graph, &Options::default());
+
+    assert!(dot.contains("sources[label=\"{ {} | { <p_0> | <p_1> } }\""));
+    let connectors = lines_with(&dot, "sources:");
+    assert_eq!(
+        connectors,
+        [
+            "sources:p_0 -> n_0 [style=dashed]",
+            "sources:p_1 -> n_0 [style=dashed]"
+        ]
+    );
+    // The wire is aligned with the interface once
+    let wires = lines_with(&dot, "n_0");
+    assert_eq!(wires.iter().filter(|line| **line == "n_0").count(), 1);
+}
+
+#[test]
+fn node_in_both_interfaces_connects_to_both() {
+    let mut graph = square();
+    graph.targets.push(graph.sources[0]);
+    let dot = render(&graph, &Options::default());
+
+    assert!(dot.contains("sources:p_0 -> n_0 [style=dashed]"));
+    assert!(dot.contains("n_0 -> targets:p_1 [style=dashed]"));
+    // Aligning a pass-through wire with both interfaces would pull them onto one rank
+    assert!(!lines_with(&dot, "n_0").contains(&"n_0"));
+}
+
+#[test]
+fn only_boundary_nodes_take_the_interface_styles() {
+    let opts = Options {
+        source_node_style: Some(WireStyle {
+            color: Some(String::from("green")),
+            ..Default::default()
+        }),
+        target_node_style: Some(WireStyle {
+            color: Some(String::from("red")),
+            width: Some(0.2),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=point xlabel=\"\\\"A\\\"\" color=\"green\"]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "n_5["),
+        ["n_5[shape=point xlabel=\"\\\"A\\\"\" color=\"red\" width=0.2]"]
+    );
+    for i in 1..5 {
+        assert_eq!(
+            lines_with(&dot, &format!("n_{}[", i)),
+            [format!("n_{}[shape=point xlabel=\"\\\"A\\\"\"]", i)]
+        );
+    }
+}
+
+#[test]
+fn highlighted_passthrough_wires() {
+    let opts = Options {
+        highlight_passthrough: true,
+        ..Default::default()
+    };
+
+    // The identity: a single wire in both interfaces
+    let mut identity = Term::empty();
+    let x = identity.new_node("A");
+    identity.sources = vec![x];
+    identity.targets = vec![x];
+    let dot = render(&identity, &opts);
+    assert_eq!(
+        lines_with(&dot, "n_0 "),
+        [
+            "sources:p_0 -> n_0 [color=\"#f0c674\" penwidth=2 weight=10]",
+            "n_0 -> targets:p_0 [color=\"#f0c674\" penwidth=2 weight=10]"
+        ]
+    );
+
+    // Other boundary wires keep their dashed connectors
+    let mut graph = copy_mul();
+    graph.targets.push(graph.sources[0]);
+    let dot = render(&graph, &opts);
+    assert!(dot.contains("n_0 -> targets:p_1 [color=\"#f0c674\" penwidth=2 weight=10]"));
+    assert!(dot.contains("n_5 -> targets:p_0 [style=dashed]"));
+    assert!(!dot.contains("sources:p_0 -> n_0 [style=dashed]"));
+}
+
+#[test]
+fn hidden_interfaces_leave_no_references() {
+    let opts = Options {
+        show_interfaces: false,
+        ..Default::default()
+    };
+    let graph = generate_dot_with(&copy_mul(), &opts);
+    let ids = all_ids(stmts(&graph));
+
+    for id in ["sources", "targets", "rank_sources", "rank_targets_wires"] {
+        assert!(!ids.iter().any(|other| other == id), "{} in {:?}", id, ids);
+    }
+    // The wires and operations are all still drawn
+    let dot = render(&copy_mul(), &opts);
+    assert_eq!(lines_with(&dot, "shape=point").len(), 6);
+    assert_eq!(lines_with(&dot, " -> e_").len(), 3);
+    assert!(!dot.contains("dashed"));
+}
+
+#[test]
+fn interface_color_tints_interfaces_and_their_connectors() {
+    let opts = Options {
+        theme: Theme {
+            interface_color: Some(String::from("#00ff00")),
+            ..Theme::default()
+        },
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "sources["),
+        ["sources[label=\"{ {} | { <p_0> } }\" shape=record style=invisible color=\"#00ff00\"]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "sources:p_0"),
+        ["sources:p_0 -> n_0 [style=dashed color=\"#00ff00\"]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "targets:p_0"),
+        ["n_5 -> targets:p_0 [style=dashed color=\"#00ff00\"]"]
+    );
+    // Operations keep the default color
+    assert_eq!(lines_with(&dot, "#00ff00").len(), 4);
+}
+
+#[test]
+fn visible_interfaces_are_labelled_boxes() {
+    let opts = Options {
+        visible_interfaces: true,
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "sources["),
+        ["sources[label=\"{ inputs | { <p_0> } }\" shape=record]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "targets["),
+        ["targets[label=\"{ { <p_0> } | outputs }\" shape=record]"]
+    );
+    assert!(!dot.contains("invisible"));
+    // Connections attach to the same ports
+    assert_eq!(
+        lines_with(&dot, "sources:p_0"),
+        ["sources:p_0 -> n_0 [style=dashed]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "targets:p_0"),
+        ["n_5 -> targets:p_0 [style=dashed]"]
+    );
+}
@@ -0,0 +1,32 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::{solarized_light, Options, Orientation};
+
+#[test]
+fn builder_starts_from_the_defaults() {
+    let opts = Options::builder().build();
+    assert_eq!(
+        render(&copy_mul(), &opts),
+        render(&copy_mul(), &Options::default())
+    );
+}
+
+#[test]
+fn builder_sets_each_field() {
+    let opts = Options::builder()
+        .orientation(Orientation::LR)
+        .theme(solarized_light())
+        .node_label(|ty: &&str| ty.to_lowercase())
+        .edge_label(|op: &&str| op.to_uppercase())
+        .build();
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(lines_with(&dot, "rankdir"), ["rankdir=LR"]);
+    assert_eq!(
+        lines_with(&dot, "bgcolor"),
+        [format!("bgcolor=\"{}\"", solarized_light().bgcolor)]
+    );
+    assert_eq!(lines_with(&dot, "n_0["), ["n_0[shape=point xlabel=\"a\"]"]);
+    assert!(lines_with(&dot, "e_1[")[0].contains("| MUL |"));
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::*;
+use dot_structures::{Edge, EdgeTy, Id, Node, NodeId, Stmt, Vertex};
+use open_hypergraphs_dot::{generate_dot_with, Options};
+
+fn canonical() -> Options<&'static str, &'static str> {
+    Options {
+        canonical_order: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn ids_are_in_natural_order() {
+    let graph = generate_dot_with(&operation("Wide", 6, 6), &canonical());
+    let stmts = stmts(&graph);
+
+    let mut expected = vec![String::from("node"), String::from("edge")];
+    expected.extend((0..12).map(|i| format!("n_{}", i)));
+    expected.push(String::from("e_0"));
+    assert_eq!(node_ids(stmts), expected);
+
+    // Edges come last, by tail then head
+    let dot = render(&operation("Wide", 6, 6), &canonical());
+    let edges: Vec<_> = dot
+        .lines()
+        .filter(|line| line.starts_with("  ") && line.contains(" -> "))
+        .map(str::trim)
+        .collect();
+    assert_eq!(edges.len(), 24);
+    assert_eq!(edges[0], "e_0:t_0 -> n_6");
+    assert_eq!(edges[5], "e_0:t_5 -> n_11");
+    assert_eq!(edges[6], "n_0 -> e_0:s_0");
+    assert_eq!(edges[16], "n_10 -> targets:p_4 [style=dashed]");
+    assert_eq!(edges[18], "sources:p_0 -> n_0 [style=dashed]");
+    // Wires in a rank subgraph are sorted too
+    let wires = find_subgraph(stmts, "rank_targets_wires").unwrap();
+    assert_eq!(
+        node_ids(&wires.stmts),
+        ["n_6", "n_7", "n_8", "n_9", "n_10", "n_11"]
+    );
+    assert!(dot.rfind("subgraph").unwrap() < dot.find(" -> ").unwrap());
+}
+
+#[test]
+fn output_does_not_depend_on_which_operation_adds_a_statement() {
+    // A note and its edge, emitted alongside one operation or the other
+    let annotated = |at: usize| Options {
+        extra_edge_stmts: Some(Box::new(move |i, _| {
+            if i != at {
+                return vec![];
+            }
+            vec![
+                Stmt::Edge(Edge {
+                    ty: EdgeTy::Pair(
+                        Vertex::N(NodeId(Id::Plain(String::from("note")), None)),
+                        Vertex::N(NodeId(Id::Plain(String::from("n_10")), None)),
+                    ),
+                    attributes: vec![],
+                }),
+                Stmt::Node(Node {
+                    id: NodeId(Id::Plain(String::from("note")), None),
+                    attributes: vec![],
+                }),
+            ]
+        })),
+        ..canonical()
+    };
+
+    let mut graph = copy_mul();
+    graph.new_operation("Wide", vec!["A"; 2], vec!["A"; 3]);
+
+    let first = render(&graph, &annotated(0));
+    assert_eq!(first, render(&graph, &annotated(2)));
+    assert_ne!(
+        render(
+            &graph,
+            &Options {
+                canonical_order: false,
+                ..annotated(0)
+            }
+        ),
+        render(
+            &graph,
+            &Options {
+                canonical_order: false,
+                ..annotated(2)
+            }
+        )
+    );
+}
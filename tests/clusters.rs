@@ -0,0 +1,53 @@
+mod common;
+
+use common::*;
+use dot_structures::{Id, Node, NodeId, Stmt};
+use open_hypergraphs_dot::{generate_dot_with, Options};
+
+#[test]
+fn operations_with_different_keys_get_separate_clusters() {
+    let opts = Options {
+        cluster_key: Box::new(|op: &&str| Some(op.to_lowercase())),
+        ..Default::default()
+    };
+    let graph = generate_dot_with(&copy_mul(), &opts);
+    let stmts = stmts(&graph);
+
+    let copy = find_subgraph(stmts, "cluster_op_0").expect("no cluster for Copy");
+    assert!(copy.stmts.contains(&attribute("label", "\"copy\"")));
+    assert_eq!(node_ids(&copy.stmts), ["e_0"]);
+
+    let mul = find_subgraph(stmts, "cluster_op_1").expect("no cluster for Mul");
+    assert!(mul.stmts.contains(&attribute("label", "\"mul\"")));
+    assert_eq!(node_ids(&mul.stmts), ["e_1"]);
+
+    assert!(!node_ids(stmts).iter().any(|id| id.starts_with("e_")));
+}
+
+#[test]
+fn extra_statements_naming_unknown_operations_stay_outside_clusters() {
+    let opts = Options {
+        cluster_key: Box::new(|_| Some(String::from("all"))),
+        extra_edge_stmts: Some(Box::new(|i, _| {
+            vec![Stmt::Node(Node {
+                id: NodeId(Id::Plain(format!("e_{}", 99 - i)), None),
+                attributes: vec![],
+            })]
+        })),
+        ..Default::default()
+    };
+    let graph = generate_dot_with(&copy_mul(), &opts);
+    let stmts = stmts(&graph);
+
+    let cluster = find_subgraph(stmts, "cluster_op_0").expect("no cluster");
+    assert_eq!(node_ids(&cluster.stmts), ["e_0", "e_1"]);
+    assert!(node_ids(stmts).contains(&String::from("e_99")));
+    assert!(node_ids(stmts).contains(&String::from("e_98")));
+}
+
+fn attribute(key: &str, value: &str) -> Stmt {
+    Stmt::Attribute(dot_structures::Attribute(
+        Id::Plain(key.to_string()),
+        Id::Plain(value.to_string()),
+    ))
+}
@@ -0,0 +1,35 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::{color_for_category, Options, PALETTE};
+
+#[test]
+fn category_colors_are_pinned() {
+    // FNV-1a of the name, with the seed mixed into the offset basis, modulo the palette size
+    assert_eq!(color_for_category("Mul", 0), PALETTE[3]);
+    assert_eq!(color_for_category("Copy", 0), PALETTE[0]);
+    assert_eq!(color_for_category("Add", 0), PALETTE[2]);
+    assert_eq!(color_for_category("", 0), PALETTE[7]);
+    assert_eq!(color_for_category("Mul", 1), PALETTE[6]);
+    assert_eq!(color_for_category("Mul", 42), PALETTE[5]);
+}
+
+#[test]
+fn edge_color_fills_operations() {
+    let opts = Options {
+        edge_color: Box::new(|op: &&str| (*op == "Mul").then(|| String::from("#ff0000"))),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "e_1["),
+        ["e_1[label=\"{ { <s_0> | <s_1> } | \\\"Mul\\\" | { <t_0> } }\" shape=record style=\"rounded,filled\" fillcolor=\"#ff0000\"]"]
+    );
+    // Operations without a color are unchanged
+    assert_eq!(
+        lines_with(&dot, "e_0["),
+        lines_with(&render(&copy_mul(), &Options::default()), "e_0[")
+    );
+    assert!(!lines_with(&dot, "e_0[")[0].contains("fillcolor"));
+}
@@ -0,0 +1,107 @@
+// Helpers shared by the integration tests; not every test file uses all of them
+#![allow(dead_code)]
+
+use dot_structures::{EdgeTy, Graph, Id, NodeId, Stmt, Subgraph, Vertex};
+use open_hypergraphs::lax::OpenHypergraph;
+use open_hypergraphs_dot::{generate_dot_with, render_dot, Options};
+
+pub type Term = OpenHypergraph<&'static str, &'static str>;
+
+/// Copy an `A`, then multiply the two copies: `Copy ; Mul`, with two quotient pairs
+pub fn copy_mul() -> Term {
+    let mut graph = Term::empty();
+    let (_, (sources, copies)) = graph.new_operation("Copy", vec!["A"], vec!["A", "A"]);
+    let (_, (inputs, targets)) = graph.new_operation("Mul", vec!["A", "A"], vec!["A"]);
+    graph.unify(copies[0], inputs[0]);
+    graph.unify(copies[1], inputs[1]);
+    graph.sources = sources;
+    graph.targets = targets;
+    graph
+}
+
+/// A single operation with the given label and numbers of source and target ports
+pub fn operation(label: &'static str, sources: usize, targets: usize) -> Term {
+    let mut graph = Term::empty();
+    let (_, (s, t)) = graph.new_operation(label, vec!["A"; sources], vec!["A"; targets]);
+    graph.sources = s;
+    graph.targets = t;
+    graph
+}
+
+/// Generate and print `graph` with `opts`
+pub fn render(graph: &Term, opts: &Options<&'static str, &'static str>) -> String {
+    render_dot(&generate_dot_with(graph, opts))
+}
+
+/// The top-level statements of a generated graph
+pub fn stmts(graph: &Graph) -> &[Stmt] {
+    match graph {
+        Graph::Graph { stmts, .. } | Graph::DiGraph { stmts, .. } => stmts,
+    }
+}
+
+/// The subgraph with the given id, searching nested subgraphs too
+pub fn find_subgraph<'a>(stmts: &'a [Stmt], id: &str) -> Option<&'a Subgraph> {
+    stmts.iter().find_map(|stmt| match stmt {
+        Stmt::Subgraph(subgraph) if subgraph.id == Id::Plain(id.to_string()) => Some(subgraph),
+        Stmt::Subgraph(subgraph) => find_subgraph(&subgraph.stmts, id),
+        _ => None,
+    })
+}
+
+/// Ids of the node statements directly in `stmts`
+pub fn node_ids(stmts: &[Stmt]) -> Vec<String> {
+    stmts
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Stmt::Node(node) => Some(node.id.0.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Every node id referenced by `stmts`, by node statements or edge endpoints, and every
+/// subgraph id, recursing into subgraphs
+pub fn all_ids(stmts: &[Stmt]) -> Vec<String> {
+    fn vertex_ids(vertex: &Vertex, ids: &mut Vec<String>) {
+        match vertex {
+            Vertex::N(NodeId(id, _)) => ids.push(id.to_string()),
+            Vertex::S(subgraph) => {
+                ids.push(subgraph.id.to_string());
+                ids.extend(all_ids(&subgraph.stmts));
+            }
+        }
+    }
+
+    let mut ids = Vec::new();
+    for stmt in stmts {
+        match stmt {
+            Stmt::Node(node) => ids.push(node.id.0.to_string()),
+            Stmt::Edge(edge) => match &edge.ty {
+                EdgeTy::Pair(from, to) => {
+                    vertex_ids(from, &mut ids);
+                    vertex_ids(to, &mut ids);
+                }
+                EdgeTy::Chain(vertices) => {
+                    for vertex in vertices {
+                        vertex_ids(vertex, &mut ids);
+                    }
+                }
+            },
+            Stmt::Subgraph(subgraph) => {
+                ids.push(subgraph.id.to_string());
+                ids.extend(all_ids(&subgraph.stmts));
+            }
+            Stmt::Attribute(_) | Stmt::GAttribute(_) => {}
+        }
+    }
+    ids
+}
+
+/// Lines of `dot` which mention `needle`, trimmed
+pub fn lines_with<'a>(dot: &'a str, needle: &str) -> Vec<&'a str> {
+    dot.lines()
+        .map(str::trim)
+        .filter(|line| line.contains(needle))
+        .collect()
+}
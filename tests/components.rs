@@ -0,0 +1,107 @@
+mod common;
+
+use common::*;
+use open_hypergraphs::lax::{Hyperedge, NodeId};
+use open_hypergraphs_dot::{generate_dot_components, render_dot, Options};
+
+/// Two components: `F: c → a`, and `G: b → d` with `e` unified with `b`
+fn two_components() -> Term {
+    let mut graph = Term::empty();
+    let [a, b, c, d, e] = ["a", "b", "c", "d", "e"].map(|label| graph.new_node(label));
+    graph.new_edge(
+        "F",
+        Hyperedge {
+            sources: vec![c],
+            targets: vec![a],
+        },
+    );
+    graph.new_edge(
+        "G",
+        Hyperedge {
+            sources: vec![b],
+            targets: vec![d],
+        },
+    );
+    graph.hypergraph.quotient = (vec![e], vec![b]);
+    graph.sources = vec![b, c];
+    graph.targets = vec![d, a];
+    graph
+}
+
+fn render_components(graph: &Term) -> Vec<String> {
+    generate_dot_components(graph, &Options::default())
+        .iter()
+        .map(render_dot)
+        .collect()
+}
+
+#[test]
+fn components_are_split_and_reindexed() {
+    let dots = render_components(&two_components());
+    assert_eq!(dots.len(), 2);
+
+    // The component of `a`, the lowest node, comes first, with nodes in their original order
+    let first = &dots[0];
+    assert!(first.contains("n_0[shape=point xlabel=\"\\\"a\\\"\"]"));
+    assert!(first.contains("n_1[shape=point xlabel=\"\\\"c\\\"\"]"));
+    assert!(first.contains("\\\"F\\\""));
+    assert!(!first.contains("\\\"G\\\""));
+    assert!(first.contains("n_1 -> e_0:s_0"));
+    assert!(first.contains("e_0:t_0 -> n_0"));
+
+    let second = &dots[1];
+    assert!(second.contains("n_0[shape=point xlabel=\"\\\"b\\\"\"]"));
+    assert!(second.contains("n_1[shape=point xlabel=\"\\\"d\\\"\"]"));
+    assert!(second.contains("n_2[shape=point xlabel=\"\\\"e\\\"\"]"));
+    assert!(second.contains("n_0 -> e_0:s_0"));
+    assert!(second.contains("e_0:t_0 -> n_1"));
+    assert!(second.contains("n_2 -> n_0 [style=\"dotted\" dir=none]"));
+}
+
+#[test]
+fn interfaces_are_distributed_across_components() {
+    let dots = render_components(&two_components());
+
+    // Global sources `[b, c]` and targets `[d, a]` each go to their own component
+    assert_eq!(
+        lines_with(&dots[0], "sources:"),
+        ["sources:p_0 -> n_1 [style=dashed]"]
+    );
+    assert_eq!(
+        lines_with(&dots[0], "targets:"),
+        ["n_0 -> targets:p_0 [style=dashed]"]
+    );
+    assert_eq!(
+        lines_with(&dots[1], "sources:"),
+        ["sources:p_0 -> n_0 [style=dashed]"]
+    );
+    assert_eq!(
+        lines_with(&dots[1], "targets:"),
+        ["n_1 -> targets:p_0 [style=dashed]"]
+    );
+
+    // A component keeps the relative order of several interface nodes
+    let mut graph = two_components();
+    graph.sources = vec![NodeId(4), NodeId(2), NodeId(1)];
+    let dots = render_components(&graph);
+    assert_eq!(
+        lines_with(&dots[1], "sources:"),
+        [
+            "sources:p_0 -> n_2 [style=dashed]",
+            "sources:p_1 -> n_0 [style=dashed]"
+        ]
+    );
+}
+
+#[test]
+fn dangling_nodes_stay_in_their_component() {
+    let mut graph = two_components();
+    graph.hypergraph.adjacency[1].targets.push(NodeId(99));
+    graph.targets.push(NodeId(98));
+
+    let dots = render_components(&graph);
+    assert_eq!(dots.len(), 3);
+    // Drawn as a phantom node past the component's three nodes
+    assert!(dots[1].contains("e_0:t_1 -> n_3"));
+    assert!(dots[2].contains("n_0 -> targets:p_0"));
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::*;
+use dot_structures::Stmt;
+use open_hypergraphs_dot::{generate_dot_with, Options};
+
+#[test]
+fn default_arrowhead_is_left_to_the_edge_defaults() {
+    let dot = render(&copy_mul(), &Options::default());
+    assert!(dot.contains("edge[fontcolor=\"white\" color=\"white\" arrowhead=none]"));
+    assert_eq!(lines_with(&dot, "arrowhead").len(), 1);
+}
+
+#[test]
+fn connection_arrowhead_is_emitted_on_connections() {
+    let opts = Options {
+        connection_arrowhead: String::from("vee"),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "[arrowhead=\"vee\"]"),
+        [
+            "n_0 -> e_0:s_0 [arrowhead=\"vee\"]",
+            "e_0:t_0 -> n_1 [arrowhead=\"vee\"]",
+            "e_0:t_1 -> n_2 [arrowhead=\"vee\"]",
+            "n_3 -> e_1:s_0 [arrowhead=\"vee\"]",
+            "n_4 -> e_1:s_1 [arrowhead=\"vee\"]",
+            "e_1:t_0 -> n_5 [arrowhead=\"vee\"]"
+        ]
+    );
+    // Interface connectors and quotient edges keep the default
+    assert!(dot.contains("sources:p_0 -> n_0 [style=dashed]"));
+    assert!(dot.contains("n_1 -> n_3 [style=\"dotted\" dir=none]"));
+}
+
+#[test]
+fn large_graphs_have_a_statement_per_element() {
+    // A chain of binary operations, each combining the previous result with a fresh input
+    let n = 2000;
+    let mut graph = Term::empty();
+    let mut acc = graph.new_node("A");
+    graph.sources.push(acc);
+    for _ in 0..n {
+        let (_, (inputs, outputs)) = graph.new_operation("Add", vec!["A", "A"], vec!["A"]);
+        graph.unify(acc, inputs[0]);
+        graph.sources.push(inputs[1]);
+        acc = outputs[0];
+    }
+    graph.targets.push(acc);
+
+    let opts = Options {
+        show_interfaces: false,
+        ..Default::default()
+    };
+    let generated = generate_dot_with(&graph, &opts);
+    let stmts = stmts(&generated);
+    let nodes = node_ids(stmts);
+    let edges = stmts
+        .iter()
+        .filter(|stmt| matches!(stmt, Stmt::Edge(_)))
+        .count();
+
+    let wires = graph.hypergraph.nodes.len();
+    assert_eq!(
+        nodes.iter().filter(|id| id.starts_with("n_")).count(),
+        wires
+    );
+    assert_eq!(nodes.iter().filter(|id| id.starts_with("e_")).count(), n);
+    // Three connections per operation, and a quotient edge for each unified pair
+    assert_eq!(edges, 3 * n + n);
+}
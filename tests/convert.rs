@@ -0,0 +1,25 @@
+mod common;
+use common::*;
+
+use dot_structures::Graph;
+use open_hypergraphs_dot::{generate_dot, render_dot, DotGraph, Options};
+
+fn print(graph: impl Into<DotGraph>) -> String {
+    render_dot(&graph.into())
+}
+
+#[test]
+fn dot_graph_converts_with_default_options() {
+    let graph = copy_mul();
+    let expected = render_dot(&generate_dot(&graph));
+
+    assert_eq!(render_dot(&DotGraph::from(&graph)), expected);
+    assert_eq!(print(&graph), expected);
+    assert_eq!(expected, render(&graph, &Options::default()));
+
+    // The inner graph is reachable by conversion and through `Deref`
+    let dot_graph = DotGraph::from(&graph);
+    assert_eq!(stmts(&dot_graph).len(), stmts(&generate_dot(&graph)).len());
+    let inner: Graph = dot_graph.into();
+    assert_eq!(render_dot(&inner), expected);
+}
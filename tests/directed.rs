@@ -0,0 +1,29 @@
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::{generate_dot_with, render_dot, write_dot, Options};
+
+#[test]
+fn undirected_graphs_use_plain_edges() {
+    let opts = Options {
+        directed: false,
+        detect_back_edges: true,
+        connection_arrowhead: String::from("vee"),
+        ..Default::default()
+    };
+    let graph = generate_dot_with(&copy_mul(), &opts);
+    let mut written = Vec::new();
+    write_dot(&graph, &mut written).unwrap();
+
+    for dot in [render_dot(&graph), String::from_utf8(written).unwrap()] {
+        assert!(dot.starts_with("graph G {"));
+        assert!(!dot.contains("->"));
+        assert!(!dot.contains("dir=none"));
+        assert!(!dot.contains("dir=back"));
+        assert!(!dot.contains("arrowhead=\"vee\""));
+        assert!(dot.contains("  n_0 -- e_0:s_0\n"));
+        assert!(dot.contains("  sources:p_0 -- n_0 [style=dashed]\n"));
+        assert!(dot.contains("  n_1 -- n_3 [style=\"dotted\"]\n"));
+        graphviz_rust::parse(&dot).expect("undirected output parses");
+    }
+}
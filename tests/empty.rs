@@ -0,0 +1,121 @@
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::*;
+
+/// Options with every feature switched on
+fn everything() -> Options<&'static str, &'static str> {
+    Options {
+        orientation: Orientation::BT,
+        theme: Theme {
+            dpi: Some(300),
+            size: Some((4.0, 3.0)),
+            fontname: Some(String::from("Fira Sans")),
+            fontsize: Some(10.0),
+            penwidth: Some(1.5),
+            interface_color: Some(String::from("gray")),
+            ..Theme::default()
+        },
+        node_is_state: Some(Box::new(|_| true)),
+        frame: Some(String::from("Empty")),
+        frame_interfaces: true,
+        sep: Some(Separation::Add(4.0)),
+        esep: Some(Separation::Scale(0.1)),
+        ranksep: Some(0.3),
+        nodesep: Some(0.2),
+        lane: Some(Box::new(|_| Some(String::from("lane")))),
+        background_grid: Some(10.0),
+        max_ports_shown: Some(2),
+        overlap: Some(Overlap::Prism),
+        detect_back_edges: true,
+        use_layers: true,
+        source_node_style: Some(WireStyle::default()),
+        target_node_style: Some(WireStyle::default()),
+        node_label_graph: Some(Box::new(|_, i| i.to_string())),
+        edge_label_graph: Some(Box::new(|_, i| i.to_string())),
+        port_compass: true,
+        minimap: true,
+        empty_label_placeholder: Some(String::from("·")),
+        interface_compass: InterfaceCompass::Auto,
+        highlight_passthrough: true,
+        extra_node_attributes: [(0, vec![(String::from("color"), String::from("red"))])].into(),
+        extra_edge_attributes: [(0, vec![(String::from("color"), String::from("red"))])].into(),
+        record_layout: RecordLayout::Spread,
+        extra_node_stmts: Some(Box::new(|_, _| vec![])),
+        extra_edge_stmts: Some(Box::new(|_, _| vec![])),
+        merge_duplicate_attachments: true,
+        rank_pin: Some(Box::new(|_| Some(RankPin::Max))),
+        minify: true,
+        highlight_path: Some((0, 1)),
+        number_operations: true,
+        number_nodes: true,
+        layers: Some(Box::new(|_| Some(0))),
+        html_labels: true,
+        strict: true,
+        graph_attributes: vec![(String::from("pad"), String::from("0.5"))],
+        legend: Some(vec![(String::from("Copy"), String::from("red"))]),
+        quotient_mode: QuotientMode::Merge,
+        label_wires: true,
+        label_ports: true,
+        cluster_key: Box::new(|_| Some(String::from("key"))),
+        highlight_edges: [0].into(),
+        highlight_nodes: [0].into(),
+        comment_header: true,
+        id_prefix: String::from("p_"),
+        label_max_width: Some(8),
+        node_label_placement: LabelPlacement::Inline,
+        connection_arrowhead: String::from("vee"),
+        concentrate: true,
+        splines: Some(Splines::Ortho),
+        directed: false,
+        edge_shape: RecordShape::Mrecord,
+        visible_interfaces: true,
+        canonical_order: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn empty_graph_has_only_defaults() {
+    let graph = generate_dot(&Term::empty());
+    let stmts = stmts(&graph);
+
+    assert_eq!(node_ids(stmts), ["node", "edge"]);
+    assert!(stmts.iter().all(|stmt| matches!(
+        stmt,
+        dot_structures::Stmt::Attribute(_) | dot_structures::Stmt::Node(_)
+    )));
+
+    let dot = render_dot(&graph);
+    assert!(dot.starts_with("digraph G {"));
+    graphviz_rust::parse(&dot).expect("empty graph output parses");
+}
+
+#[test]
+fn empty_graph_with_every_option() {
+    let graph = Term::empty();
+    let opts = everything();
+
+    let outputs = [
+        print_dot_with(&graph, &opts),
+        print_dot(&generate_dot_transposed(&graph, &opts), &opts),
+        print_dot(&generate_dot_quotient_only(&graph, &opts), &opts),
+        print_dot(
+            &generate_dot_many(&[("a", &graph), ("b", &graph)], &opts),
+            &opts,
+        ),
+    ];
+    for dot in outputs {
+        // Drop the comment header, which the parser doesn't accept
+        let dot = dot
+            .lines()
+            .filter(|line| !line.starts_with("//"))
+            .collect::<String>();
+        graphviz_rust::parse(&dot).expect("empty graph output parses");
+        assert!(!dot.contains("sources"));
+        assert!(!dot.contains("targets"));
+        assert!(!dot.contains("minimap"));
+    }
+
+    assert!(generate_dot_components(&graph, &opts).is_empty());
+}
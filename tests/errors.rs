@@ -0,0 +1,121 @@
+mod common;
+
+use common::*;
+use open_hypergraphs::lax::{Hyperedge, NodeId};
+use open_hypergraphs_dot::{
+    generate_dot, render_dot, try_generate_dot, GenerateError, Options, QuotientMode,
+};
+
+/// `copy_mul` with `change` applied, checking that `generate_dot` still draws it, with a phantom
+/// node `n_99` standing in for the missing node
+fn dangling(change: impl FnOnce(&mut Term)) -> Term {
+    let mut graph = copy_mul();
+    change(&mut graph);
+    let dot = render(&graph, &Options::default());
+    assert!(dot.contains("n_99"), "no phantom node in {}", dot);
+    graph
+}
+
+#[test]
+fn valid_graph_passes() {
+    assert!(try_generate_dot(&copy_mul()).is_ok());
+}
+
+#[test]
+fn missing_edge_source() {
+    let graph = dangling(|g| g.hypergraph.adjacency[1].sources[1] = NodeId(99));
+    assert_eq!(
+        try_generate_dot(&graph).unwrap_err(),
+        GenerateError::EdgeSource {
+            edge: 1,
+            port: 1,
+            node: 99
+        }
+    );
+}
+
+#[test]
+fn missing_edge_target() {
+    let graph = dangling(|g| g.hypergraph.adjacency[0].targets[0] = NodeId(99));
+    assert_eq!(
+        try_generate_dot(&graph).unwrap_err(),
+        GenerateError::EdgeTarget {
+            edge: 0,
+            port: 0,
+            node: 99
+        }
+    );
+}
+
+#[test]
+fn missing_interface_source() {
+    let graph = dangling(|g| g.sources.push(NodeId(99)));
+    assert_eq!(
+        try_generate_dot(&graph).unwrap_err(),
+        GenerateError::InterfaceSource { port: 1, node: 99 }
+    );
+}
+
+#[test]
+fn missing_interface_target() {
+    let graph = dangling(|g| g.targets.insert(0, NodeId(99)));
+    assert_eq!(
+        try_generate_dot(&graph).unwrap_err(),
+        GenerateError::InterfaceTarget { port: 0, node: 99 }
+    );
+}
+
+#[test]
+fn missing_quotient_node() {
+    let graph = dangling(|g| {
+        g.hypergraph.quotient.0.push(NodeId(0));
+        g.hypergraph.quotient.1.push(NodeId(99));
+    });
+    assert_eq!(
+        try_generate_dot(&graph).unwrap_err(),
+        GenerateError::Quotient { index: 2, node: 99 }
+    );
+}
+
+#[test]
+fn adjacency_length_mismatch() {
+    let mut graph = copy_mul();
+    graph.hypergraph.adjacency.push(Hyperedge {
+        sources: vec![],
+        targets: vec![],
+    });
+    assert_eq!(
+        try_generate_dot(&graph).unwrap_err(),
+        GenerateError::AdjacencyLength {
+            edges: 2,
+            adjacency: 3
+        }
+    );
+}
+
+#[test]
+fn dangling_nodes_render_with_node_options() {
+    let mut graph = copy_mul();
+    graph.sources.push(NodeId(97));
+    graph.targets.push(NodeId(98));
+    graph.hypergraph.adjacency[0].sources.push(NodeId(99));
+    graph.hypergraph.quotient.0.push(NodeId(96));
+    graph.hypergraph.quotient.1.push(NodeId(0));
+
+    assert!(render_dot(&generate_dot(&graph)).contains("n_99"));
+
+    for mode in [QuotientMode::Edges, QuotientMode::Merge] {
+        let opts = Options {
+            quotient_mode: mode,
+            label_ports: true,
+            label_wires: true,
+            wire_color: Box::new(|_| Some(String::from("red"))),
+            highlight_path: Some((0, 5)),
+            ..Default::default()
+        };
+        let dot = render(&graph, &opts);
+        assert!(dot.contains("n_99"));
+        assert!(dot.contains("n_97"));
+        assert!(dot.contains("n_98"));
+    }
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "exec")]
+
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::{export_frames, DotError, Options};
+use std::process::Command;
+
+/// Whether the GraphViz `dot` binary can be run
+fn has_dot() -> bool {
+    Command::new("dot").arg("-V").output().is_ok()
+}
+
+#[test]
+fn export_frames_writes_numbered_pngs() {
+    let dir = std::env::temp_dir().join(format!("frames-{}", std::process::id()));
+    let graphs = [copy_mul(), operation("Square", 1, 1)];
+    let result = export_frames(&graphs, &Options::default(), &dir);
+
+    if !has_dot() {
+        // Without GraphViz the error says so, rather than failing some other way
+        match result {
+            Err(DotError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+            other => panic!("expected a missing `dot` error, got {:?}", other),
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+        return;
+    }
+
+    let paths = result.expect("frames export");
+    assert_eq!(
+        paths,
+        [dir.join("frame_000.png"), dir.join("frame_001.png")]
+    );
+    for path in &paths {
+        let bytes = std::fs::read(path).expect("frame written");
+        assert!(bytes.starts_with(b"\x89PNG"));
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,84 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::Options;
+
+fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn extra_attributes_apply_to_the_keyed_elements() {
+    let opts = Options {
+        extra_node_attributes: [(0, pairs(&[("tooltip", "input"), ("shape", "circle")]))].into(),
+        extra_edge_attributes: [(1, pairs(&[("tooltip", "mul #1")]))].into(),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    // Values are quoted, and replace generated attributes of the same name in place
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=\"circle\" xlabel=\"\\\"A\\\"\" tooltip=\"input\"]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "n_1["),
+        ["n_1[shape=point xlabel=\"\\\"A\\\"\"]"]
+    );
+    assert!(lines_with(&dot, "e_1[")[0].ends_with("shape=record tooltip=\"mul #1\"]"));
+    assert!(!lines_with(&dot, "e_0[")[0].contains("tooltip"));
+}
+
+#[test]
+fn out_of_range_keys_are_ignored() {
+    let opts = Options {
+        extra_node_attributes: [(99, pairs(&[("color", "red")]))].into(),
+        extra_edge_attributes: [(99, pairs(&[("color", "red")]))].into(),
+        ..Default::default()
+    };
+    assert_eq!(
+        render(&copy_mul(), &opts),
+        render(&copy_mul(), &Options::default())
+    );
+}
+
+#[test]
+fn node_attributes_override_the_defaults() {
+    let opts = Options {
+        node_label: Box::new(|ty: &&str| ty.to_string()),
+        node_attributes: Box::new(|ty: &&str| {
+            pairs(&[("color", "#ff0000"), ("xlabel", &format!("{}!", ty))])
+        }),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=point xlabel=\"A!\" color=\"#ff0000\"]"]
+    );
+    assert_eq!(lines_with(&dot, "color=\"#ff0000\"").len(), 6);
+}
+
+#[test]
+fn edge_attributes_are_appended_and_escaped() {
+    let opts = Options {
+        edge_attributes: Box::new(|op: &&str| {
+            pairs(&[
+                ("tooltip", &format!("the \"{}\" operation", op)),
+                (
+                    "href",
+                    &format!("https://example.com/{}", op.to_lowercase()),
+                ),
+            ])
+        }),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert!(lines_with(&dot, "e_1[")[0].ends_with(
+        "shape=record tooltip=\"the \\\"Mul\\\" operation\" href=\"https://example.com/mul\"]"
+    ));
+    graphviz_rust::parse(&dot).expect("escaped attribute values parse");
+}
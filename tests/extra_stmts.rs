@@ -0,0 +1,55 @@
+mod common;
+use common::*;
+
+use dot_structures::{Attribute, Edge, EdgeTy, Id, Node, NodeId, Stmt, Vertex};
+use open_hypergraphs_dot::{generate_dot_with, Options};
+
+fn note(id: String, label: &str) -> Stmt {
+    Stmt::Node(Node {
+        id: NodeId(Id::Plain(id), None),
+        attributes: vec![Attribute(
+            Id::Plain(String::from("label")),
+            Id::Plain(format!("\"{}\"", label)),
+        )],
+    })
+}
+
+#[test]
+fn extra_statements_follow_their_element() {
+    let opts = Options {
+        extra_node_stmts: Some(Box::new(|i, ty: &&str| {
+            if i == 5 {
+                vec![note(String::from("result"), ty)]
+            } else {
+                vec![]
+            }
+        })),
+        extra_edge_stmts: Some(Box::new(|i, op: &&str| {
+            if *op != "Mul" {
+                return vec![];
+            }
+            vec![
+                note(format!("note_{}", i), "multiplies"),
+                Stmt::Edge(Edge {
+                    ty: EdgeTy::Pair(
+                        Vertex::N(NodeId(Id::Plain(format!("note_{}", i)), None)),
+                        Vertex::N(NodeId(Id::Plain(format!("e_{}", i)), None)),
+                    ),
+                    attributes: vec![],
+                }),
+            ]
+        })),
+        ..Default::default()
+    };
+    let graph = generate_dot_with(&copy_mul(), &opts);
+    let ids = node_ids(stmts(&graph));
+
+    let position = |id: &str| ids.iter().position(|other| other == id).unwrap();
+    assert_eq!(position("result"), position("n_5") + 1);
+    assert_eq!(position("note_1"), position("e_1") + 1);
+    assert_eq!(ids.iter().filter(|id| id.starts_with("note_")).count(), 1);
+
+    let dot = render(&copy_mul(), &opts);
+    assert_eq!(lines_with(&dot, "result"), ["result[label=\"A\"]"]);
+    assert_eq!(lines_with(&dot, "note_1 ->"), ["note_1 -> e_1"]);
+}
@@ -0,0 +1,46 @@
+use open_hypergraphs::lax::var::HasVar;
+use open_hypergraphs::lax::OpenHypergraph;
+use open_hypergraphs_dot::{generate_dot_forget, generate_dot_with, render_dot, Options};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Gate {
+    Copy,
+    And,
+}
+
+impl HasVar for Gate {
+    fn var() -> Gate {
+        Gate::Copy
+    }
+}
+
+/// `x ↦ x ∧ x`, copying the input explicitly
+fn and_self() -> OpenHypergraph<(), Gate> {
+    let mut graph = OpenHypergraph::empty();
+    let (_, (sources, copies)) = graph.new_operation(Gate::Copy, vec![()], vec![(), ()]);
+    let (_, (inputs, targets)) = graph.new_operation(Gate::And, vec![(), ()], vec![()]);
+    graph.unify(copies[0], inputs[0]);
+    graph.unify(copies[1], inputs[1]);
+    graph.sources = sources;
+    graph.targets = targets;
+    graph
+}
+
+#[test]
+fn forget_replaces_copies_with_wires() {
+    let dot = render_dot(&generate_dot_forget(&and_self(), &Options::default()));
+
+    assert!(!dot.contains("Copy"));
+    assert_eq!(dot.matches("shape=point").count(), 2);
+    // Both inputs of `And` read the source wire directly
+    assert!(dot.contains("n_0 -> e_0:s_0\n"));
+    assert!(dot.contains("n_0 -> e_0:s_1\n"));
+    assert!(dot.contains("e_0:t_0 -> n_1\n"));
+}
+
+#[test]
+fn graphs_are_drawn_as_given_by_default() {
+    let dot = render_dot(&generate_dot_with(&and_self(), &Options::default()));
+    assert!(dot.contains("| Copy |"));
+    assert!(dot.contains("dotted"));
+}
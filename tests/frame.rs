@@ -0,0 +1,47 @@
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::{generate_dot_with, Options};
+
+fn framed(frame_interfaces: bool) -> Options<&'static str, &'static str> {
+    Options {
+        frame: Some(String::from("Copy; Mul")),
+        frame_interfaces,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn frame_wraps_the_diagram() {
+    let graph = generate_dot_with(&copy_mul(), &framed(true));
+    let stmts = stmts(&graph);
+
+    // Only the header defaults and the frame are left at the top level
+    assert_eq!(node_ids(stmts), ["node", "edge"]);
+    assert_eq!(stmts.len(), 5);
+
+    let frame = find_subgraph(stmts, "cluster_frame").expect("no frame");
+    let ids = all_ids(&frame.stmts);
+    for id in ["n_0", "n_5", "e_0", "e_1", "sources", "targets"] {
+        assert!(ids.contains(&id.to_string()), "{} not in the frame", id);
+    }
+
+    let dot = render(&copy_mul(), &framed(true));
+    assert!(dot.contains("label=\"Copy; Mul\"\n    color=\"#f0c674\"\n"));
+}
+
+#[test]
+fn frame_can_leave_out_the_interfaces() {
+    let graph = generate_dot_with(&copy_mul(), &framed(false));
+    let stmts = stmts(&graph);
+
+    let frame = find_subgraph(stmts, "cluster_frame").expect("no frame");
+    let inside = all_ids(&frame.stmts);
+    assert!(inside.contains(&String::from("e_0")));
+    assert!(!inside.contains(&String::from("sources")));
+    assert!(!inside.contains(&String::from("targets")));
+
+    let outside = all_ids(stmts);
+    assert!(outside.contains(&String::from("sources")));
+    assert!(outside.contains(&String::from("targets")));
+}
@@ -0,0 +1,204 @@
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::{Options, Overlap, Separation, Splines, Theme};
+
+/// The graph attribute lines of the output for `opts`
+fn attributes(opts: &Options<&'static str, &'static str>) -> Vec<String> {
+    let dot = render(&copy_mul(), opts);
+    dot.lines()
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with("node["))
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
+#[test]
+fn strict_graphs_use_the_strict_keyword() {
+    let dot = render(&copy_mul(), &Options::default());
+    assert!(dot.starts_with("digraph G {"));
+
+    let opts = Options {
+        strict: true,
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert!(dot.starts_with("strict digraph G {"));
+    graphviz_rust::parse(&dot).expect("strict output parses");
+
+    let opts = Options {
+        strict: true,
+        directed: false,
+        ..Default::default()
+    };
+    assert!(render(&copy_mul(), &opts).starts_with("strict graph G {"));
+}
+
+#[test]
+fn dpi_and_size_are_emitted_only_when_set() {
+    assert_eq!(
+        attributes(&Options::default()),
+        ["rankdir=TB", "bgcolor=\"#4a4a4a\""]
+    );
+
+    let opts = Options {
+        theme: Theme {
+            dpi: Some(300),
+            size: Some((4.0, 3.5)),
+            ..Theme::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(
+        attributes(&opts),
+        [
+            "rankdir=TB",
+            "bgcolor=\"#4a4a4a\"",
+            "dpi=300",
+            "size=\"4,3.5\""
+        ]
+    );
+
+    let opts = Options {
+        theme: Theme {
+            dpi: Some(150),
+            ..Theme::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(attributes(&opts)[2..], ["dpi=150"]);
+}
+
+#[test]
+fn sep_and_esep_strings() {
+    assert_eq!(Separation::Scale(0.5).to_dot().as_deref(), Some("0.5"));
+    assert_eq!(Separation::Add(4.0).to_dot().as_deref(), Some("+4"));
+    assert_eq!(Separation::Add(-1.0).to_dot(), None);
+    assert_eq!(Separation::Scale(f64::NAN).to_dot(), None);
+
+    let opts = Options {
+        sep: Some(Separation::Add(4.0)),
+        esep: Some(Separation::Scale(0.25)),
+        ..Default::default()
+    };
+    assert_eq!(attributes(&opts)[2..], ["sep=\"+4\"", "esep=\"0.25\""]);
+
+    // Invalid margins are dropped
+    let opts = Options {
+        sep: Some(Separation::Add(-4.0)),
+        esep: Some(Separation::Scale(f64::INFINITY)),
+        ..Default::default()
+    };
+    assert_eq!(attributes(&opts).len(), 2);
+}
+
+#[test]
+fn overlap_values() {
+    let values = [
+        (Overlap::Retain, "true"),
+        (Overlap::Remove, "false"),
+        (Overlap::Prism, "prism"),
+        (Overlap::Scale, "scale"),
+        (Overlap::ScaleXY, "scalexy"),
+        (Overlap::Compress, "compress"),
+        (Overlap::Vpsc, "vpsc"),
+        (Overlap::Ortho, "ortho"),
+    ];
+    for (overlap, value) in values {
+        let opts = Options {
+            overlap: Some(overlap),
+            ..Default::default()
+        };
+        assert_eq!(attributes(&opts)[2..], [format!("overlap={}", value)]);
+    }
+}
+
+#[test]
+fn ranksep_and_nodesep_values() {
+    let opts = Options {
+        ranksep: Some(0.3),
+        nodesep: Some(0.125),
+        ..Default::default()
+    };
+    assert_eq!(attributes(&opts)[2..], ["ranksep=0.3", "nodesep=0.125"]);
+
+    let opts = Options {
+        nodesep: Some(1.0),
+        ..Default::default()
+    };
+    assert_eq!(attributes(&opts)[2..], ["nodesep=1"]);
+}
+
+#[test]
+fn splines_values() {
+    assert!(!render(&copy_mul(), &Options::default()).contains("splines"));
+
+    let values = [
+        (Splines::Line, "line"),
+        (Splines::Curved, "curved"),
+        (Splines::Ortho, "ortho"),
+        (Splines::Polyline, "polyline"),
+    ];
+    for (splines, value) in values {
+        let opts = Options {
+            splines: Some(splines),
+            ..Default::default()
+        };
+        assert_eq!(attributes(&opts)[2..], [format!("splines={}", value)]);
+    }
+}
+
+#[test]
+fn transparent_theme_background() {
+    let opts = Options {
+        theme: Theme::transparent(),
+        ..Default::default()
+    };
+    assert_eq!(attributes(&opts), ["rankdir=TB", "bgcolor=\"transparent\""]);
+
+    let opts = Options {
+        theme: Theme {
+            bgcolor: String::from("none"),
+            ..Theme::default()
+        },
+        minify: true,
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    // The keyword survives unquoting
+    assert_eq!(lines_with(&dot, "bgcolor"), ["bgcolor=none"]);
+    graphviz_rust::parse(&dot).expect("minified output parses");
+}
+
+#[test]
+fn custom_graph_attributes_follow_the_built_in_ones() {
+    let opts = Options {
+        graph_attributes: vec![
+            (String::from("pad"), String::from("0.5")),
+            (String::from("fontcolor"), String::from("#ff0000")),
+            (String::from("label"), String::from("a \"caption\"")),
+        ],
+        ..Default::default()
+    };
+    assert_eq!(
+        attributes(&opts),
+        [
+            "rankdir=TB",
+            "bgcolor=\"#4a4a4a\"",
+            "pad=\"0.5\"",
+            "fontcolor=\"#ff0000\"",
+            "label=\"a \\\"caption\\\"\""
+        ]
+    );
+    graphviz_rust::parse(&render(&copy_mul(), &opts)).expect("graph attributes parse");
+}
+
+#[test]
+fn concentrate_is_emitted_only_when_set() {
+    let opts = Options {
+        concentrate: true,
+        ..Default::default()
+    };
+    assert_eq!(attributes(&opts)[2..], ["concentrate=true"]);
+    assert!(!render(&copy_mul(), &Options::default()).contains("concentrate"));
+}
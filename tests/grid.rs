@@ -0,0 +1,42 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::Options;
+
+fn grid(spacing: f64) -> Options<&'static str, &'static str> {
+    Options {
+        background_grid: Some(spacing),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn background_grid_draws_lines_at_the_spacing() {
+    // Three elements give an estimated extent of five inches, or 360 points
+    let dot = render(&operation("f", 1, 1), &grid(2.5));
+    assert_eq!(
+        lines_with(&dot, "_background"),
+        ["_background=\"c 10 -#80808040 \
+          L 2 0 0 0 360 L 2 0 0 360 0 \
+          L 2 180 0 180 360 L 2 0 180 360 180 \
+          L 2 360 0 360 360 L 2 0 360 360 360\""]
+    );
+}
+
+#[test]
+fn background_grid_grows_with_the_graph() {
+    let dot = render(&copy_mul(), &grid(0.5));
+    let grid = lines_with(&dot, "_background")[0];
+    // Eight elements give 720 points, with a line each way every 36 points
+    assert_eq!(grid.matches(" L 2 ").count(), 2 * 21);
+    assert!(grid.ends_with("L 2 0 720 720 720\""));
+}
+
+#[test]
+fn invalid_spacing_draws_no_grid() {
+    for spacing in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let dot = render(&copy_mul(), &grid(spacing));
+        assert!(!dot.contains("_background"), "grid for {}", spacing);
+    }
+    assert!(!render(&copy_mul(), &Options::default()).contains("_background"));
+}
@@ -0,0 +1,34 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::{generate_dot_with, print_dot, print_dot_with, Options};
+
+#[test]
+fn comment_header_starts_the_output() {
+    let graph = copy_mul();
+    let opts = Options {
+        comment_header: true,
+        ..Default::default()
+    };
+    let dot = print_dot_with(&graph, &opts);
+    let (header, rest) = dot.split_once('\n').unwrap();
+
+    assert_eq!(
+        header,
+        format!(
+            "// open-hypergraphs-dot v{}: 6 nodes, 2 edges",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+    assert_eq!(rest, print_dot(&generate_dot_with(&graph, &opts), &opts));
+}
+
+#[test]
+fn no_header_by_default() {
+    let graph = copy_mul();
+    let opts = Options::default();
+    let dot = print_dot_with(&graph, &opts);
+
+    assert!(!dot.starts_with("//"));
+    assert_eq!(dot, print_dot(&generate_dot_with(&graph, &opts), &opts));
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::Options;
+
+#[test]
+fn spiders_and_copies_take_highlights_and_extra_attributes() {
+    let opts = Options {
+        is_spider: Box::new(|op: &&str| (*op == "Mul").then(|| String::from("green"))),
+        is_copy: Box::new(|op: &&str| *op == "Copy"),
+        highlight_edges: [0, 1].into(),
+        extra_edge_attributes: [(1, vec![(String::from("tooltip"), String::from("mul"))])].into(),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "e_0["),
+        ["e_0[shape=point width=0.08 color=\"#f0c674\" penwidth=2]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "e_1["),
+        ["e_1[shape=circle style=filled fillcolor=\"green\" label=\"\" width=0.2 color=\"#f0c674\" penwidth=2 tooltip=\"mul\"]"]
+    );
+}
+
+#[test]
+fn highlight_nodes_changes_only_the_selected_nodes() {
+    let plain = render(&copy_mul(), &Options::default());
+    let opts = Options {
+        highlight_nodes: [1, 5, 42].into(),
+        ..Default::default()
+    };
+    let highlighted = render(&copy_mul(), &opts);
+
+    let changed: Vec<_> = plain
+        .lines()
+        .zip(highlighted.lines())
+        .filter(|(before, after)| before != after)
+        .map(|(_, after)| after.trim())
+        .collect();
+    assert_eq!(
+        changed,
+        [
+            "n_1[shape=point xlabel=\"\\\"A\\\"\" color=\"#f0c674\" width=0.15]",
+            "n_5[shape=point xlabel=\"\\\"A\\\"\" color=\"#f0c674\" width=0.15]"
+        ]
+    );
+    assert_eq!(plain.lines().count(), highlighted.lines().count());
+}
+
+#[test]
+fn highlight_path_marks_the_shortest_path() {
+    let opts = Options {
+        highlight_path: Some((0, 5)),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    // Highlighted nodes and operations, then connections and quotient edges by their tails
+    let highlighted: Vec<_> = dot
+        .lines()
+        .filter(|line| line.contains("color=\"#f0c674\" penwidth=2"))
+        .map(|line| line.trim().split(['[', ' ']).next().unwrap())
+        .collect();
+    assert_eq!(
+        highlighted,
+        ["n_0", "n_1", "n_3", "n_5", "e_0", "e_1", "n_0", "e_0:t_0", "n_3", "e_1:t_0", "n_1"]
+    );
+    // The other copy isn't on the path
+    assert_eq!(
+        lines_with(&dot, "n_2 -> n_4"),
+        ["n_2 -> n_4 [style=\"dotted\" dir=none]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "n_4["),
+        ["n_4[shape=point xlabel=\"\\\"A\\\"\"]"]
+    );
+}
+
+#[test]
+fn highlight_path_without_a_path_changes_nothing() {
+    let plain = render(&copy_mul(), &Options::default());
+    for path in [(5, 0), (0, 42), (42, 0)] {
+        let opts = Options {
+            highlight_path: Some(path),
+            ..Default::default()
+        };
+        assert_eq!(render(&copy_mul(), &opts), plain, "{:?}", path);
+    }
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use common::*;
+use dot_structures::{Id, Node, NodeId, Stmt};
+use open_hypergraphs_dot::{generate_dot_many, generate_dot_with, Options};
+
+#[test]
+fn id_prefix_applies_to_every_id() {
+    let opts = Options {
+        id_prefix: String::from("p_"),
+        minimap: true,
+        legend: Some(vec![(String::from("Copy"), String::from("red"))]),
+        ..Default::default()
+    };
+    let graph = generate_dot_with(&copy_mul(), &opts);
+    let stmts = stmts(&graph);
+
+    let defaults = ["node", "edge", "graph"];
+    for id in all_ids(stmts) {
+        if defaults.contains(&id.as_str()) {
+            continue;
+        }
+        let id = id.strip_prefix("cluster").unwrap_or(&id);
+        assert!(id.starts_with("p_"), "unprefixed id {}", id);
+    }
+
+    // The theme defaults are still default attribute statements, not nodes
+    let ids = node_ids(stmts);
+    assert!(ids.contains(&String::from("node")));
+    assert!(ids.contains(&String::from("edge")));
+    assert!(!ids.iter().any(|id| id.ends_with("node") && id != "node"));
+    assert!(!ids.iter().any(|id| id.ends_with("edge") && id != "edge"));
+
+    let minimap = find_subgraph(stmts, "clusterp__minimap").expect("minimap cluster");
+    assert!(node_ids(&minimap.stmts).contains(&String::from("edge")));
+}
+
+#[test]
+fn id_prefix_applies_to_graph_id() {
+    let opts = Options {
+        id_prefix: String::from("p_"),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert!(dot.starts_with("digraph p_G {"));
+}
+
+#[test]
+fn generate_dot_many_wraps_each_graph_in_its_cluster() {
+    let opts = Options {
+        minimap: true,
+        ..Default::default()
+    };
+    let before = copy_mul();
+    let after = operation("Square", 1, 1);
+    let graph = generate_dot_many(&[("before", &before), ("after", &after)], &opts);
+    let stmts = stmts(&graph);
+
+    for (name, graph) in [("before", &before), ("after", &after)] {
+        let cluster = find_subgraph(stmts, &format!("cluster_{}", name)).expect("cluster");
+        let ids = node_ids(&cluster.stmts);
+
+        // Every wire, operation and interface of the graph is in its cluster, under its name
+        for i in 0..graph.hypergraph.nodes.len() {
+            assert!(ids.contains(&format!("{}_n_{}", name, i)));
+        }
+        for i in 0..graph.hypergraph.edges.len() {
+            assert!(ids.contains(&format!("{}_e_{}", name, i)));
+        }
+        let interfaces = all_ids(&cluster.stmts);
+        assert!(interfaces.contains(&format!("{}_sources", name)));
+        assert!(interfaces.contains(&format!("{}_targets", name)));
+
+        // Defaults shared with the enclosing graph are not repeated as stray nodes
+        assert!(!ids
+            .iter()
+            .any(|id| id.ends_with("node") || id.ends_with("edge")));
+
+        let minimap = find_subgraph(&cluster.stmts, &format!("cluster{}__minimap", name))
+            .expect("minimap cluster");
+        assert!(node_ids(&minimap.stmts).contains(&String::from("edge")));
+        assert!(!all_ids(&minimap.stmts).contains(&format!("{}_edge", name)));
+    }
+}
+
+#[test]
+fn id_prefix_goes_inside_quoted_ids() {
+    let opts = Options {
+        id_prefix: String::from("p"),
+        extra_edge_stmts: Some(Box::new(|i, _| {
+            let id = match i {
+                0 => String::from("\"my note\""),
+                _ => String::from("\"say \\\"hi\\\"\""),
+            };
+            vec![Stmt::Node(Node {
+                id: NodeId(Id::Plain(id), None),
+                attributes: vec![],
+            })]
+        })),
+        ..Default::default()
+    };
+    let ids = node_ids(stmts(&generate_dot_with(&copy_mul(), &opts)));
+
+    assert!(ids.contains(&String::from("\"pmy note\"")));
+    assert!(ids.contains(&String::from("\"psay \\\"hi\\\"\"")));
+    // Generated ids are still prefixed without quotes
+    assert!(ids.contains(&String::from("pe_0")));
+}
+
+#[test]
+fn graph_id_names_the_graph() {
+    assert!(render(&copy_mul(), &Options::default()).starts_with("digraph G {"));
+
+    let named = |graph_id: &str| Options {
+        graph_id: graph_id.to_string(),
+        ..Default::default()
+    };
+    assert!(render(&copy_mul(), &named("before")).starts_with("digraph before {"));
+
+    // Ids which are not plain identifiers are quoted
+    let dot = render(&copy_mul(), &named("my \"graph\""));
+    assert!(dot.starts_with("digraph \"my \\\"graph\\\"\" {"), "{}", dot);
+    graphviz_rust::parse(&dot).expect("quoted graph id parses");
+    assert!(render(&copy_mul(), &named("graph")).starts_with("digraph \"graph\" {"));
+}
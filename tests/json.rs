@@ -0,0 +1,59 @@
+#![cfg(feature = "serde_json")]
+mod common;
+
+use common::*;
+use open_hypergraphs::lax::{Hyperedge, NodeId, OpenHypergraph};
+use open_hypergraphs_dot::{generate_json, Options};
+use serde_json::Value;
+
+/// Rebuild a hypergraph labelled by strings from the output of `generate_json`
+fn from_json(json: &Value) -> OpenHypergraph<String, String> {
+    let ids = |value: &Value| -> Vec<NodeId> {
+        let ids = value.as_array().expect("node ids are an array");
+        ids.iter()
+            .map(|id| NodeId(id.as_u64().unwrap() as usize))
+            .collect()
+    };
+
+    let mut graph = OpenHypergraph::empty();
+    for (i, node) in json["nodes"].as_array().unwrap().iter().enumerate() {
+        assert_eq!(node["id"], i);
+        graph.new_node(node["label"].as_str().unwrap().to_string());
+    }
+    for (i, edge) in json["edges"].as_array().unwrap().iter().enumerate() {
+        assert_eq!(edge["id"], i);
+        graph.new_edge(
+            edge["label"].as_str().unwrap().to_string(),
+            Hyperedge {
+                sources: ids(&edge["sources"]),
+                targets: ids(&edge["targets"]),
+            },
+        );
+    }
+    for pair in json["quotient"].as_array().unwrap() {
+        let pair = ids(pair);
+        graph.hypergraph.quotient.0.push(pair[0]);
+        graph.hypergraph.quotient.1.push(pair[1]);
+    }
+    graph.sources = ids(&json["sources"]);
+    graph.targets = ids(&json["targets"]);
+    graph
+}
+
+#[test]
+fn json_round_trips() {
+    let json = generate_json(&copy_mul(), &Options::default());
+    let value: Value = serde_json::from_str(&json).expect("output is valid JSON");
+
+    assert_eq!(value["sources"], serde_json::json!([0]));
+    assert_eq!(value["targets"], serde_json::json!([5]));
+    assert_eq!(value["quotient"], serde_json::json!([[1, 3], [2, 4]]));
+    assert_eq!(value["edges"][0]["targets"], serde_json::json!([1, 2]));
+
+    let rebuilt = from_json(&value);
+    let labels = Options::builder()
+        .node_label(|label: &String| label.clone())
+        .edge_label(|label: &String| label.clone())
+        .build();
+    assert_eq!(generate_json(&rebuilt, &labels), json);
+}
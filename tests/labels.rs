@@ -0,0 +1,416 @@
+mod common;
+
+use common::*;
+use open_hypergraphs::lax::OpenHypergraph;
+use open_hypergraphs_dot::{
+    escape_dot_label, generate_dot_with, render_dot, EdgeLabeller, LabelPlacement, LabelStyle,
+    NodeLabeller, Options,
+};
+
+#[test]
+fn escape_dot_label_escapes_line_breaks_and_tabs() {
+    assert_eq!(escape_dot_label("a\nb"), "a\\nb");
+    assert_eq!(escape_dot_label("a\r\nb"), "a\\nb");
+    assert_eq!(escape_dot_label("a\tb"), "a    b");
+}
+
+#[test]
+fn multiline_labels_become_line_break_escapes() {
+    let opts = Options::builder()
+        .node_label(|_: &&str| String::from("x\ny"))
+        .edge_label(|op: &&str| format!("{}\r\nop", op))
+        .build();
+    let dot = render(&copy_mul(), &opts);
+
+    assert!(dot.contains("n_0[shape=point xlabel=\"x\\ny\"]"));
+    assert!(dot.contains("e_1[label=\"{ { <s_0> | <s_1> } | Mul\\nop | { <t_0> } }\""));
+    // Every statement stays on its own line
+    assert_eq!(
+        dot.lines().filter(|line| line.contains("xlabel")).count(),
+        6
+    );
+    graphviz_rust::parse(&dot).expect("output with multiline labels parses");
+}
+
+/// The attributes of the single operation in `operation("X", sources, targets)` for `opts`, from
+/// the value of its label on
+fn operation_attributes(
+    opts: &Options<&'static str, &'static str>,
+    sources: usize,
+    targets: usize,
+) -> String {
+    let dot = render(&operation("X", sources, targets), opts);
+    let line = lines_with(&dot, "e_0[").remove(0);
+    line.strip_prefix("e_0[label=").unwrap().to_string()
+}
+
+#[test]
+fn empty_operation_labels_for_each_port_count() {
+    let unlabelled = Options::builder()
+        .edge_label(|_: &&str| String::new())
+        .build();
+    let cases = [
+        ((0, 0), "\"\"", "\"·\""),
+        ((1, 0), "\"{ { <s_0> } |  }\"", "\"{ { <s_0> } | · }\""),
+        ((0, 1), "\"{  | { <t_0> } }\"", "\"{ · | { <t_0> } }\""),
+        (
+            (2, 1),
+            "\"{ { <s_0> | <s_1> } |  | { <t_0> } }\"",
+            "\"{ { <s_0> | <s_1> } | · | { <t_0> } }\"",
+        ),
+    ];
+    let placeholder = Options {
+        empty_label_placeholder: Some(String::from("·")),
+        ..Options::builder()
+            .edge_label(|_: &&str| String::new())
+            .build()
+    };
+
+    for ((sources, targets), empty, with_placeholder) in cases {
+        assert_eq!(
+            operation_attributes(&unlabelled, sources, targets),
+            format!("{} shape=record]", empty)
+        );
+        assert_eq!(
+            operation_attributes(&placeholder, sources, targets),
+            format!("{} shape=record]", with_placeholder)
+        );
+    }
+
+    let html = Options {
+        html_labels: true,
+        ..placeholder
+    };
+    assert!(operation_attributes(&html, 0, 0).contains("<TR><TD>·</TD></TR>"));
+    assert!(operation_attributes(&html, 2, 1).contains("<TR><TD>·</TD></TR>"));
+
+    // Non-empty labels are left alone
+    let labelled = Options {
+        empty_label_placeholder: Some(String::from("·")),
+        ..Default::default()
+    };
+    assert_eq!(
+        operation_attributes(&labelled, 1, 0),
+        "\"{ { <s_0> } | \\\"X\\\" }\" shape=record]"
+    );
+}
+
+#[test]
+fn long_labels_wrap_at_word_boundaries() {
+    let opts = Options {
+        label_max_width: Some(10),
+        ..Options::builder()
+            .node_label(|_: &&str| String::from("a wire with a long name"))
+            .edge_label(|op: &&str| format!("{} of several long words and_an_overlong_word", op))
+            .build()
+    };
+    let dot = render(&operation("Mul", 1, 1), &opts);
+
+    assert!(dot.contains("n_0[shape=point xlabel=\"a wire\\nwith a\\nlong name\"]"));
+    // Words longer than the width are kept whole
+    assert!(dot.contains("| Mul of\\nseveral\\nlong words\\nand_an_overlong_word |"));
+
+    // Labels are left alone by default
+    let opts = Options {
+        label_max_width: None,
+        ..opts
+    };
+    let dot = render(&operation("Mul", 1, 1), &opts);
+    assert!(dot.contains("xlabel=\"a wire with a long name\""));
+}
+
+#[test]
+fn empty_node_labels_omit_the_xlabel() {
+    let opts = Options::builder()
+        .node_label(|_: &&str| String::new())
+        .build();
+    let dot = render(&copy_mul(), &opts);
+
+    for i in 0..6 {
+        assert_eq!(
+            lines_with(&dot, &format!("n_{}[", i)),
+            [format!("n_{}[shape=point]", i)]
+        );
+    }
+    assert!(!dot.contains("xlabel"));
+}
+
+#[test]
+fn wire_labels_match_the_node_labels() {
+    let opts = Options {
+        label_wires: true,
+        number_nodes: true,
+        node_label_graph: Some(Box::new(|_, i| format!("w{}", i))),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert!(dot.contains("n_0[shape=point xlabel=\"[0] w0\"]"));
+    assert_eq!(
+        lines_with(&dot, "n_0 -> e_0"),
+        ["n_0 -> e_0:s_0 [label=\"[0] w0\"]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "e_1:t_0"),
+        ["e_1:t_0 -> n_5 [label=\"[5] w5\"]"]
+    );
+}
+
+#[test]
+fn port_labels_match_the_wire_labels() {
+    let opts = Options {
+        label_ports: true,
+        label_wires: true,
+        node_label_graph: Some(Box::new(|_, i| format!("w{}", i))),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert!(dot.contains("e_1[label=\"{ { <s_0> w3 | <s_1> w4 } | \\\"Mul\\\" | { <t_0> w5 } }\""));
+    assert!(dot.contains("n_3 -> e_1:s_0 [label=\"w3\"]"));
+}
+
+#[derive(Debug)]
+#[allow(dead_code)]
+struct Config {
+    width: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)]
+enum Op {
+    Mul(u8),
+    Add { width: u8 },
+    Nop,
+}
+
+#[test]
+fn label_style_formats_values() {
+    let config = Config { width: 8 };
+    assert_eq!(LabelStyle::Debug.format(&config), "Config { width: 8 }");
+    assert_eq!(LabelStyle::VariantOnly.format(&config), "Config");
+    assert_eq!(LabelStyle::VariantOnly.format(&Op::Mul(8)), "Mul");
+    assert_eq!(LabelStyle::VariantOnly.format(&Op::Add { width: 8 }), "Add");
+    assert_eq!(LabelStyle::VariantOnly.format(&Op::Nop), "Nop");
+    assert_eq!(LabelStyle::VariantOnly.format(&Some(1)), "Some");
+}
+
+#[test]
+fn variant_only_labels_show_the_constructor() {
+    let mut graph = OpenHypergraph::<Option<u8>, Op>::empty();
+    graph.new_operation(Op::Add { width: 8 }, vec![Some(8)], vec![None]);
+
+    let opts = Options::with_label_style(LabelStyle::VariantOnly);
+    let dot = render_dot(&generate_dot_with(&graph, &opts));
+    assert_eq!(
+        lines_with(&dot, "e_0["),
+        ["e_0[label=\"{ { <s_0> } | Add | { <t_0> } }\" shape=record]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=point xlabel=\"Some\"]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "n_1["),
+        ["n_1[shape=point xlabel=\"None\"]"]
+    );
+}
+
+#[test]
+fn graph_labels_see_the_whole_graph() {
+    let opts = Options {
+        node_label: Box::new(|_: &&str| String::from("ignored")),
+        edge_label: Box::new(|_: &&str| String::from("ignored")),
+        node_label_graph: Some(Box::new(|graph: &Term, i| {
+            match graph.targets.iter().position(|node| node.0 == i) {
+                Some(k) => format!("output {} of {}", k + 1, graph.targets.len()),
+                None => String::from("wire"),
+            }
+        })),
+        edge_label_graph: Some(Box::new(|graph: &Term, i| {
+            format!(
+                "{} ({} in)",
+                graph.hypergraph.edges[i],
+                graph.hypergraph.adjacency[i].sources.len()
+            )
+        })),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "n_5["),
+        ["n_5[shape=point xlabel=\"output 1 of 1\"]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=point xlabel=\"wire\"]"]
+    );
+    assert!(lines_with(&dot, "e_1[")[0].contains("| Mul (2 in) |"));
+    assert!(!dot.contains("ignored"));
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Ty(&'static str);
+
+impl std::fmt::Display for Ty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[test]
+fn display_labels() {
+    let mut graph = OpenHypergraph::<Ty, Ty>::empty();
+    graph.new_operation(Ty("Neg"), vec![Ty("Int")], vec![Ty("Int")]);
+
+    let dot = render_dot(&generate_dot_with(&graph, &Options::with_display()));
+    assert_eq!(
+        lines_with(&dot, "e_0["),
+        ["e_0[label=\"{ { <s_0> } | Neg | { <t_0> } }\" shape=record]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=point xlabel=\"Int\"]"]
+    );
+
+    // The default still uses Debug
+    let dot = render_dot(&generate_dot_with(&graph, &Options::default()));
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=point xlabel=\"Ty(\\\"Int\\\")\"]"]
+    );
+}
+
+#[test]
+fn numbering_prefixes_the_element_index() {
+    let opts = Options {
+        number_operations: true,
+        edge_label: Box::new(|op: &&str| op.to_string()),
+        node_label: Box::new(|ty: &&str| ty.to_string()),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert!(lines_with(&dot, "e_0[")[0].contains("| [0] Copy |"));
+    assert!(lines_with(&dot, "e_1[")[0].contains("| [1] Mul |"));
+    assert_eq!(lines_with(&dot, "n_4["), ["n_4[shape=point xlabel=\"A\"]"]);
+
+    let opts = Options {
+        number_operations: false,
+        number_nodes: true,
+        ..opts
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert_eq!(
+        lines_with(&dot, "n_4["),
+        ["n_4[shape=point xlabel=\"[4] A\"]"]
+    );
+    assert!(lines_with(&dot, "e_1[")[0].contains("| Mul |"));
+}
+
+/// A labeller configured by value, which can be cloned into several options
+#[derive(Clone)]
+struct Prefixed(&'static str);
+
+impl NodeLabeller<&'static str> for Prefixed {
+    fn label(&self, node: &&'static str) -> String {
+        format!("{}{}", self.0, node)
+    }
+}
+
+impl EdgeLabeller<&'static str> for Prefixed {
+    fn label(&self, edge: &&'static str) -> String {
+        format!("{}{}", self.0, edge.to_lowercase())
+    }
+}
+
+#[test]
+fn named_labellers_can_be_reused() {
+    let labeller = Prefixed("x:");
+    let opts = Options::builder()
+        .node_labeller(labeller.clone())
+        .edge_labeller(labeller.clone())
+        .build();
+    let dot = render(&copy_mul(), &opts);
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=point xlabel=\"x:A\"]"]
+    );
+    assert!(lines_with(&dot, "e_1[")[0].contains("| x:mul |"));
+
+    // The same labeller configures another graph's options
+    let opts = Options {
+        node_label: Box::new(labeller.clone()),
+        edge_label: Box::new(labeller),
+        ..Default::default()
+    };
+    let dot = render(&operation("Neg", 1, 1), &opts);
+    assert!(lines_with(&dot, "e_0[")[0].contains("| x:neg |"));
+}
+
+#[test]
+fn html_labels_draw_tables_with_port_cells() {
+    let opts = Options {
+        html_labels: true,
+        edge_label: Box::new(|op: &&str| format!("<{}> & \"co\"", op)),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    let table = "<TABLE BORDER=\"0\" CELLBORDER=\"1\" CELLSPACING=\"0\">";
+    assert_eq!(
+        lines_with(&dot, "e_0["),
+        [format!(
+            "e_0[label=<<TABLE BORDER=\"1\" CELLBORDER=\"0\" CELLSPACING=\"0\" STYLE=\"ROUNDED\">\
+             <TR><TD>{table}<TR><TD PORT=\"s_0\"></TD></TR></TABLE></TD></TR>\
+             <TR><TD>&lt;Copy&gt; &amp; &quot;co&quot;</TD></TR>\
+             <TR><TD>{table}<TR><TD PORT=\"t_0\"></TD><TD PORT=\"t_1\"></TD></TR></TABLE></TD></TR>\
+             </TABLE>> shape=plain]"
+        )]
+    );
+    // Connections still attach to the port cells
+    assert_eq!(
+        lines_with(&dot, "-> e_1"),
+        ["n_3 -> e_1:s_0", "n_4 -> e_1:s_1"]
+    );
+    graphviz_rust::parse(&dot).expect("output with HTML labels parses");
+}
+
+#[test]
+fn inline_node_labels_replace_the_point() {
+    let placement = |node_label_placement| Options {
+        node_label_placement,
+        node_label: Box::new(|ty: &&str| ty.to_string()),
+        ..Default::default()
+    };
+
+    let dot = render(&copy_mul(), &placement(LabelPlacement::Xlabel));
+    assert_eq!(lines_with(&dot, "n_0["), ["n_0[shape=point xlabel=\"A\"]"]);
+
+    let dot = render(&copy_mul(), &placement(LabelPlacement::Inline));
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=plaintext label=\"A\" margin=0 width=0 height=0]"]
+    );
+    assert!(!dot.contains("xlabel"));
+}
+
+#[test]
+fn custom_escape_applies_to_every_label() {
+    let opts = Options {
+        escape: Box::new(|label: &str| label.replace('"', "'")),
+        label_ports: true,
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=point xlabel=\"'A'\"]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "e_0["),
+        ["e_0[label=\"{ { <s_0> 'A' } | 'Copy' | { <t_0> 'A' | <t_1> 'A' } }\" shape=record]"]
+    );
+    assert!(!dot.contains("\\\""));
+}
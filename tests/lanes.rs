@@ -0,0 +1,71 @@
+mod common;
+use common::*;
+
+use dot_structures::{Attribute, Id, Stmt};
+use open_hypergraphs_dot::{generate_dot_with, Options};
+
+/// Unconnected operations, each with one source and one target
+fn operations(labels: &[&'static str]) -> Term {
+    let mut graph = Term::empty();
+    for label in labels {
+        graph.new_operation(*label, vec!["A"], vec!["A"]);
+    }
+    graph
+}
+
+fn lanes() -> Options<&'static str, &'static str> {
+    Options {
+        lane: Some(Box::new(|op: &&str| match *op {
+            "Load" | "Store" => Some(String::from("memory")),
+            "Add" => Some(String::from("alu")),
+            _ => None,
+        })),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn operations_are_grouped_into_lanes_in_order_of_appearance() {
+    let graph = generate_dot_with(&operations(&["Load", "Add", "Nop", "Store"]), &lanes());
+    let stmts = stmts(&graph);
+
+    let memory = find_subgraph(stmts, "lane_band_0").expect("no memory lane");
+    assert_eq!(
+        memory.stmts[0],
+        Stmt::Attribute(Attribute(
+            Id::Plain(String::from("rank")),
+            Id::Plain(String::from("same"))
+        ))
+    );
+    assert_eq!(node_ids(&memory.stmts), ["lane_0", "e_0", "e_3"]);
+
+    let alu = find_subgraph(stmts, "lane_band_1").expect("no alu lane");
+    assert_eq!(node_ids(&alu.stmts), ["lane_1", "e_1"]);
+
+    // Operations without a lane are not placed in any band
+    assert!(find_subgraph(stmts, "lane_band_2").is_none());
+}
+
+#[test]
+fn lanes_are_labelled_and_chained() {
+    let dot = render(&operations(&["Load", "Add", "Store"]), &lanes());
+
+    assert_eq!(
+        lines_with(&dot, "lane_0["),
+        ["lane_0[shape=plaintext label=\"memory\"]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "lane_1["),
+        ["lane_1[shape=plaintext label=\"alu\"]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "lane_0 ->"),
+        ["lane_0 -> lane_1 [style=invis]"]
+    );
+}
+
+#[test]
+fn no_lanes_by_default() {
+    let dot = render(&operations(&["Load", "Add"]), &Options::default());
+    assert!(!dot.contains("lane"));
+}
@@ -0,0 +1,65 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::Options;
+
+fn layered() -> Options<&'static str, &'static str> {
+    Options {
+        use_layers: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn labels_and_structure_are_on_separate_layers() {
+    let dot = render(&copy_mul(), &layered());
+
+    assert_eq!(lines_with(&dot, "layers="), ["layers=\"structure:labels\""]);
+    // Wires carry their labels, operations keep their text with the structure
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=point xlabel=\"\\\"A\\\"\" layer=labels]"]
+    );
+    assert!(lines_with(&dot, "e_0[")[0].ends_with("layer=structure]"));
+    assert_eq!(
+        lines_with(&dot, "n_0 -> e_0"),
+        ["n_0 -> e_0:s_0 [layer=structure]"]
+    );
+    assert!(lines_with(&dot, "n_1 -> n_3")[0].ends_with("layer=structure]"));
+}
+
+#[test]
+fn references_to_wires_keep_their_layer() {
+    let dot = render(&copy_mul(), &layered());
+
+    // The interface wires are also named in rank subgraphs, which must not move them
+    for line in lines_with(&dot, "n_0")
+        .into_iter()
+        .chain(lines_with(&dot, "n_5"))
+    {
+        assert!(
+            !line.starts_with("n_0[layer") && !line.starts_with("n_5[layer"),
+            "{}",
+            line
+        );
+    }
+    assert!(lines_with(&dot, "n_0").contains(&"n_0"));
+}
+
+#[test]
+fn unlabelled_wires_are_structure() {
+    let opts = Options {
+        node_label: Box::new(|_: &&str| String::new()),
+        ..layered()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert_eq!(
+        lines_with(&dot, "n_0["),
+        ["n_0[shape=point layer=structure]"]
+    );
+}
+
+#[test]
+fn no_layers_by_default() {
+    assert!(!render(&copy_mul(), &Options::default()).contains("layer"));
+}
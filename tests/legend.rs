@@ -0,0 +1,39 @@
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::{generate_dot_with, Options};
+
+#[test]
+fn legend_cluster_has_a_node_per_entry() {
+    let opts = Options {
+        legend: Some(vec![
+            (String::from("Copy"), String::from("red")),
+            (String::from("Mul"), String::from("#00ff00")),
+        ]),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert!(dot
+        .contains("legend_0[shape=box style=\"rounded,filled\" fillcolor=\"red\" label=\"Copy\"]"));
+    assert!(dot.contains(
+        "legend_1[shape=box style=\"rounded,filled\" fillcolor=\"#00ff00\" label=\"Mul\"]"
+    ));
+
+    let graph = generate_dot_with(&copy_mul(), &opts);
+    let stmts = stmts(&graph);
+    let legend = find_subgraph(stmts, "cluster_legend").expect("no legend cluster");
+    assert_eq!(node_ids(&legend.stmts), ["legend_0", "legend_1"]);
+    // The legend is kept apart from the diagram
+    assert!(!node_ids(stmts).iter().any(|id| id.starts_with("legend")));
+}
+
+#[test]
+fn empty_legend_is_skipped() {
+    for legend in [None, Some(vec![])] {
+        let opts = Options {
+            legend,
+            ..Default::default()
+        };
+        assert!(!render(&copy_mul(), &opts).contains("legend"));
+    }
+}
@@ -0,0 +1,68 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::{generate_mermaid, Options, Orientation};
+
+fn lines(mermaid: &str) -> Vec<&str> {
+    mermaid.lines().map(str::trim).collect()
+}
+
+#[test]
+fn wires_are_small_circles_with_labelled_connections() {
+    let opts = Options {
+        node_label: Box::new(|ty: &&str| ty.to_string()),
+        edge_label: Box::new(|op: &&str| op.to_string()),
+        ..Default::default()
+    };
+    let mermaid = generate_mermaid(&copy_mul(), &opts);
+    let lines = lines(&mermaid);
+
+    assert_eq!(lines[0], "flowchart TD");
+    for i in 0..6 {
+        assert!(lines.contains(&format!("n_{i}@{{ shape: sm-circ }}").as_str()));
+    }
+    assert!(lines.contains(&"e_0[[\"Copy\"]]"));
+    assert!(lines.contains(&"e_1[[\"Mul\"]]"));
+
+    // The wire type is carried by every arrow touching the wire
+    assert!(lines.contains(&"n_0 -->|\"A\"| e_0"));
+    assert!(lines.contains(&"e_0 -->|\"A\"| n_1"));
+    assert!(lines.contains(&"e_1 -->|\"A\"| n_5"));
+    assert!(lines.contains(&"inputs -->|\"A\"| n_0"));
+    assert!(lines.contains(&"n_5 -->|\"A\"| outputs"));
+    assert!(lines.contains(&"n_1 -.- n_3"));
+}
+
+#[test]
+fn unlabelled_wires_have_plain_arrows() {
+    let opts = Options {
+        node_label: Box::new(|_: &&str| String::new()),
+        orientation: Orientation::LR,
+        ..Default::default()
+    };
+    let mermaid = generate_mermaid(&copy_mul(), &opts);
+    let lines = lines(&mermaid);
+
+    assert_eq!(lines[0], "flowchart LR");
+    assert!(lines.contains(&"n_0 --> e_0"));
+    assert!(!mermaid.contains('|'));
+}
+
+#[test]
+fn labels_are_escaped() {
+    // The default labels are Debug-formatted, so they contain quotes
+    let mermaid = generate_mermaid(&copy_mul(), &Options::default());
+    let lines = lines(&mermaid);
+    assert!(lines.contains(&"e_0[[\"#quot;Copy#quot;\"]]"));
+    assert!(lines.contains(&"n_0 -->|\"#quot;A#quot;\"| e_0"));
+
+    let opts = Options {
+        edge_label: Box::new(|_: &&str| String::from("<a#b>\nc")),
+        ..Default::default()
+    };
+    let mermaid = generate_mermaid(&operation("f", 1, 1), &opts);
+    assert_eq!(
+        lines_with(&mermaid, "e_0[["),
+        ["e_0[[\"#lt;a#35;b#gt;<br/>c\"]]"]
+    );
+}
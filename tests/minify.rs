@@ -0,0 +1,50 @@
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::{
+    generate_dot_quotient_only, generate_dot_with, print_dot, Options, Theme,
+};
+
+fn minified() -> Options<&'static str, &'static str> {
+    Options {
+        minify: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn minified_output_parses() {
+    let opts = Options {
+        frame: Some(String::from("A framed \"term\"")),
+        legend: Some(vec![(String::from("Copy"), String::from("#ff0000"))]),
+        theme: Theme {
+            fontname: Some(String::from("Fira Sans")),
+            ..Theme::default()
+        },
+        ..minified()
+    };
+    let dot = print_dot(&generate_dot_with(&copy_mul(), &opts), &opts);
+
+    assert!(!dot.contains('\n'));
+    assert!(dot.contains("fontcolor=white"));
+    // Values which aren't identifiers keep their quotes
+    assert!(dot.contains("\"Fira Sans\""));
+    assert!(dot.contains("\"#ff0000\""));
+
+    let parsed = graphviz_rust::parse(&dot).expect("minified output parses");
+    assert_eq!(
+        print_dot(&parsed, &opts),
+        dot,
+        "minified output changes when parsed and printed again"
+    );
+}
+
+#[test]
+fn minified_quotient_only_output_parses() {
+    let opts = minified();
+    let dot = print_dot(&generate_dot_quotient_only(&copy_mul(), &opts), &opts);
+
+    assert!(dot.contains("fontcolor=white"));
+    assert!(!dot.contains("fontcolor=\"white\""));
+    graphviz_rust::parse(&dot).expect("minified output parses");
+}
@@ -0,0 +1,53 @@
+mod common;
+use common::*;
+
+use dot_structures::{Graph, Id};
+use open_hypergraphs_dot::{generate_dot_with, render_dot, Options, QuotientMode};
+
+/// The minimap of `graph`, printed on its own
+fn minimap(graph: &Term, quotient_mode: QuotientMode) -> String {
+    let opts = Options {
+        minimap: true,
+        quotient_mode,
+        ..Default::default()
+    };
+    let dot = generate_dot_with(graph, &opts);
+    let minimap = find_subgraph(stmts(&dot), "cluster_minimap").expect("minimap cluster");
+    render_dot(&Graph::DiGraph {
+        id: Id::Plain(String::from("minimap")),
+        strict: false,
+        stmts: minimap.stmts.clone(),
+    })
+}
+
+#[test]
+fn minimap_draws_each_quotient_pair_once() {
+    let mut graph = copy_mul();
+    // A repeated pair, the same pair reversed, and a reflexive pair
+    let (lefts, rights) = &mut graph.hypergraph.quotient;
+    let (left, right) = (lefts[0], rights[0]);
+    lefts.extend([left, right, graph.sources[0]]);
+    rights.extend([right, left, graph.sources[0]]);
+
+    let minimap = minimap(&graph, QuotientMode::Edges);
+    assert_eq!(
+        lines_with(&minimap, "style=dotted"),
+        [
+            "m_n_1 -> m_n_3 [style=dotted]",
+            "m_n_2 -> m_n_4 [style=dotted]",
+        ]
+    );
+}
+
+#[test]
+fn minimap_merges_nodes_like_the_main_drawing() {
+    let minimap = minimap(&copy_mul(), QuotientMode::Merge);
+
+    assert!(lines_with(&minimap, "m_n_3").is_empty());
+    assert!(lines_with(&minimap, "m_n_4").is_empty());
+    assert!(lines_with(&minimap, "dotted").is_empty());
+    assert_eq!(
+        lines_with(&minimap, "-> m_e_1"),
+        ["m_n_1 -> m_e_1", "m_n_2 -> m_e_1"]
+    );
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::{InterfaceCompass, Options, Orientation};
+
+/// Each orientation, with its `rankdir` and the compass sides where the flow enters and leaves
+const ORIENTATIONS: [(Orientation, &str, &str, &str); 4] = [
+    (Orientation::LR, "LR", "w", "e"),
+    (Orientation::TB, "TB", "n", "s"),
+    (Orientation::RL, "RL", "e", "w"),
+    (Orientation::BT, "BT", "s", "n"),
+];
+
+#[test]
+fn each_orientation_sets_rankdir() {
+    for (orientation, rankdir, _, _) in ORIENTATIONS {
+        let opts = Options {
+            orientation,
+            ..Default::default()
+        };
+        let dot = render(&copy_mul(), &opts);
+        assert_eq!(
+            lines_with(&dot, "rankdir"),
+            [format!("rankdir={}", rankdir)]
+        );
+    }
+}
+
+#[test]
+fn port_compass_follows_the_orientation() {
+    for (orientation, _, incoming, outgoing) in ORIENTATIONS {
+        let opts = Options {
+            orientation,
+            port_compass: true,
+            ..Default::default()
+        };
+        let dot = render(&copy_mul(), &opts);
+        assert_eq!(
+            lines_with(&dot, "e_1:"),
+            [
+                format!("n_3 -> e_1:s_0:{}", incoming),
+                format!("n_4 -> e_1:s_1:{}", incoming),
+                format!("e_1:t_0:{} -> n_5", outgoing)
+            ]
+        );
+    }
+
+    // Without it, GraphViz picks the side
+    let dot = render(&copy_mul(), &Options::default());
+    assert_eq!(lines_with(&dot, "e_1:t_0"), ["e_1:t_0 -> n_5"]);
+}
+
+#[test]
+fn interface_compass_follows_the_orientation() {
+    for (orientation, _, incoming, outgoing) in ORIENTATIONS {
+        let opts = Options {
+            orientation,
+            interface_compass: InterfaceCompass::Auto,
+            ..Default::default()
+        };
+        let dot = render(&copy_mul(), &opts);
+        assert!(dot.contains(&format!(
+            "sources:p_0:{} -> n_0:{} [style=dashed]",
+            outgoing, incoming
+        )));
+        assert!(dot.contains(&format!(
+            "n_5:{} -> targets:p_0:{} [style=dashed]",
+            outgoing, incoming
+        )));
+    }
+}
+
+#[test]
+fn custom_interface_compass() {
+    let opts = Options {
+        interface_compass: InterfaceCompass::Custom {
+            tail: String::from("se"),
+            head: String::from("nw"),
+        },
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert!(dot.contains("sources:p_0:se -> n_0:nw [style=dashed]"));
+    assert!(dot.contains("n_5:se -> targets:p_0:nw [style=dashed]"));
+
+    // Unset leaves the connectors attached to the ports alone
+    let dot = render(&copy_mul(), &Options::default());
+    assert!(dot.contains("sources:p_0 -> n_0 [style=dashed]"));
+    assert!(dot.contains("n_5 -> targets:p_0 [style=dashed]"));
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::{OperationDisplay, Options, RecordLayout, RecordShape};
+
+#[test]
+fn wide_operations_summarize_hidden_ports() {
+    let opts = Options {
+        max_ports_shown: Some(2),
+        ..Default::default()
+    };
+    let dot = render(&operation("Big", 6, 3), &opts);
+
+    assert!(dot.contains(
+        "e_0[label=\"{ { <s_0> | <s_1> | <s_more> +4 more } | \\\"Big\\\" | { <t_0> | <t_1> | <t_more> +1 more } }\""
+    ));
+    // Wires to hidden ports are bundled into the summary cell
+    assert_eq!(
+        lines_with(&dot, "-> e_0:"),
+        [
+            "n_0 -> e_0:s_0",
+            "n_1 -> e_0:s_1",
+            "n_2 -> e_0:s_more",
+            "n_3 -> e_0:s_more",
+            "n_4 -> e_0:s_more",
+            "n_5 -> e_0:s_more"
+        ]
+    );
+    assert_eq!(lines_with(&dot, "e_0:t_more"), ["e_0:t_more -> n_8"]);
+    // The interfaces still show every port
+    assert!(dot.contains("<p_5>"));
+
+    let opts = Options {
+        html_labels: true,
+        ..opts
+    };
+    let dot = render(&operation("Big", 6, 3), &opts);
+    assert!(dot.contains("<TD PORT=\"s_more\">+4 more</TD>"));
+    assert!(dot.contains("<TD PORT=\"t_more\">+1 more</TD>"));
+}
+
+#[test]
+fn operations_within_the_limit_show_every_port() {
+    let opts = Options {
+        max_ports_shown: Some(3),
+        ..Default::default()
+    };
+    let dot = render(&operation("Add", 3, 1), &opts);
+    assert!(!dot.contains("more"));
+    assert!(dot.contains("{ <s_0> | <s_1> | <s_2> }"));
+}
+
+#[test]
+fn mrecord_shape() {
+    let opts = Options {
+        edge_shape: RecordShape::Mrecord,
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert!(dot
+        .contains("e_0[label=\"{ { <s_0> } | \\\"Copy\\\" | { <t_0> | <t_1> } }\" shape=Mrecord]"));
+    assert!(dot
+        .contains("e_1[label=\"{ { <s_0> | <s_1> } | \\\"Mul\\\" | { <t_0> } }\" shape=Mrecord]"));
+    // Ports are referenced the same way
+    assert!(dot.contains("n_3 -> e_1:s_0"));
+
+    let dot = render(&copy_mul(), &Options::default());
+    assert!(!dot.contains("Mrecord"));
+}
+
+#[test]
+fn compact_arity_replaces_port_cells() {
+    let opts = Options {
+        operation_display: OperationDisplay::CompactArity,
+        edge_label: Box::new(|op: &&str| op.to_string()),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "e_0["),
+        ["e_0[label=\"Copy (1→2)\" shape=record]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "e_1["),
+        ["e_1[label=\"Mul (2→1)\" shape=record]"]
+    );
+    // Connections attach to the box, not to ports
+    assert_eq!(
+        lines_with(&dot, "e_1"),
+        [
+            "e_1[label=\"Mul (2→1)\" shape=record]",
+            "n_3 -> e_1",
+            "n_4 -> e_1",
+            "e_1 -> n_5"
+        ]
+    );
+}
+
+#[test]
+fn record_layouts() {
+    let layout = |record_layout| Options {
+        record_layout,
+        edge_label: Box::new(|op: &&str| op.to_string()),
+        ..Default::default()
+    };
+
+    let dot = render(&copy_mul(), &layout(RecordLayout::Stacked));
+    assert_eq!(
+        lines_with(&dot, "e_1["),
+        ["e_1[label=\"{ { <s_0> | <s_1> } | Mul | { <t_0> } }\" shape=record]"]
+    );
+
+    let dot = render(&copy_mul(), &layout(RecordLayout::Spread));
+    assert_eq!(
+        lines_with(&dot, "e_1["),
+        ["e_1[label=\"{ <s_0> | <s_1> } | Mul | { <t_0> }\" shape=record]"]
+    );
+    // Port names, and so connections, don't depend on the layout
+    assert_eq!(
+        lines_with(&dot, "-> e_1"),
+        ["n_3 -> e_1:s_0", "n_4 -> e_1:s_1"]
+    );
+}
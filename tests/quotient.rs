@@ -0,0 +1,157 @@
+mod common;
+
+use common::*;
+use open_hypergraphs::lax::Hyperedge;
+use open_hypergraphs_dot::{
+    generate_dot_quotient_only, generate_dot_with, render_dot, Options, QuotientMode, QuotientStyle,
+};
+
+#[test]
+fn default_quotient_style_is_dotted() {
+    let dot = render(&copy_mul(), &Options::default());
+    assert_eq!(
+        lines_with(&dot, "n_1 -> n_3"),
+        ["n_1 -> n_3 [style=\"dotted\" dir=none]"]
+    );
+}
+
+#[test]
+fn quotient_style_sets_emitted_attributes() {
+    let opts = Options {
+        quotient_style: QuotientStyle {
+            color: Some(String::from("red")),
+            style: String::from("dashed,bold"),
+            penwidth: Some(0.5),
+        },
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "n_1 -> n_3"),
+        ["n_1 -> n_3 [style=\"dashed,bold\" dir=none color=\"red\" penwidth=0.5]"]
+    );
+    graphviz_rust::parse(&dot).expect("output with a combined style parses");
+}
+
+#[test]
+fn reflexive_quotient_pairs_are_not_drawn() {
+    let mut graph = copy_mul();
+    graph.hypergraph.quotient.0.push(graph.sources[0]);
+    graph.hypergraph.quotient.1.push(graph.sources[0]);
+
+    for mode in [QuotientMode::Edges, QuotientMode::Merge] {
+        let opts = Options {
+            quotient_mode: mode,
+            detect_back_edges: true,
+            ..Default::default()
+        };
+        let dot = render(&graph, &opts);
+        assert!(
+            lines_with(&dot, "n_0 -> n_0").is_empty(),
+            "self-loop in {}",
+            dot
+        );
+    }
+    // The other pairs are still drawn
+    let dot = render(&graph, &Options::default());
+    assert_eq!(lines_with(&dot, "n_1 -> n_3").len(), 1);
+}
+
+#[test]
+fn self_loop_operations_render() {
+    let mut graph = Term::empty();
+    let x = graph.new_node("A");
+    graph.new_edge(
+        "Step",
+        Hyperedge {
+            sources: vec![x],
+            targets: vec![x],
+        },
+    );
+    graph.hypergraph.quotient.0.push(x);
+    graph.hypergraph.quotient.1.push(x);
+
+    let opts = Options {
+        detect_back_edges: true,
+        ..Default::default()
+    };
+    let dot = render(&graph, &opts);
+    // The input wire is a back edge, drawn reversed to keep the operation's ranks ordered
+    assert_eq!(lines_with(&dot, "e_0:s_0"), ["e_0:s_0 -> n_0 [dir=back]"]);
+    assert_eq!(lines_with(&dot, "e_0:t_0"), ["e_0:t_0 -> n_0"]);
+    assert!(lines_with(&dot, "n_0 -> n_0").is_empty());
+}
+
+#[test]
+fn merge_mode_draws_one_node_per_wire() {
+    let opts = Options {
+        quotient_mode: QuotientMode::Merge,
+        ..Default::default()
+    };
+    let graph = generate_dot_with(&copy_mul(), &opts);
+    assert_eq!(
+        node_ids(stmts(&graph)),
+        ["node", "edge", "n_0", "n_1", "n_2", "n_5", "e_0", "e_1"]
+    );
+
+    let dot = render(&copy_mul(), &opts);
+    assert!(!dot.contains("n_3"));
+    assert!(!dot.contains("n_4"));
+    // Connections to the merged nodes go to their representatives instead
+    assert_eq!(
+        lines_with(&dot, "-> e_1"),
+        ["n_1 -> e_1:s_0", "n_2 -> e_1:s_1"]
+    );
+    assert!(!dot.contains("dotted"));
+}
+
+#[test]
+fn quotient_only_draws_just_the_wiring() {
+    let graph = generate_dot_quotient_only(&copy_mul(), &Options::default());
+    let stmts = stmts(&graph);
+    let ids = all_ids(stmts);
+
+    // Every node is drawn as a point, and no operation appears
+    for i in 0..6 {
+        assert!(ids.contains(&format!("n_{}", i)));
+    }
+    assert!(!ids.iter().any(|id| id.starts_with("e_")));
+
+    let dot = render_dot(&graph);
+    assert_eq!(lines_with(&dot, "shape=point").len(), 6);
+    assert_eq!(
+        lines_with(&dot, "dotted"),
+        [
+            "n_1 -> n_3 [style=\"dotted\" dir=none]",
+            "n_2 -> n_4 [style=\"dotted\" dir=none]"
+        ]
+    );
+    // The interface is kept
+    assert_eq!(
+        lines_with(&dot, "sources:p_0 ->"),
+        ["sources:p_0 -> n_0 [style=dashed]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "-> targets"),
+        ["n_5 -> targets:p_0 [style=dashed]"]
+    );
+}
+
+#[test]
+fn quotient_pairs_are_drawn_once_either_way_round() {
+    let mut graph = copy_mul();
+    let (lefts, rights) = &mut graph.hypergraph.quotient;
+    let (left, right) = (lefts[0], rights[0]);
+    lefts.extend([left, right]);
+    rights.extend([right, left]);
+
+    let dot = render(&graph, &Options::default());
+    assert_eq!(
+        lines_with(&dot, "dotted"),
+        [
+            "n_1 -> n_3 [style=\"dotted\" dir=none]",
+            "n_2 -> n_4 [style=\"dotted\" dir=none]"
+        ]
+    );
+}
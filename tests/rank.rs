@@ -0,0 +1,129 @@
+mod common;
+
+use common::*;
+use dot_structures::{Attribute, Id, Stmt};
+use open_hypergraphs_dot::{generate_dot_with, Options, RankPin};
+
+#[test]
+fn each_rank_pin_emits_its_constraint() {
+    let pins = [
+        (RankPin::Min, "min"),
+        (RankPin::Max, "max"),
+        (RankPin::Source, "source"),
+        (RankPin::Sink, "sink"),
+    ];
+    for (pin, rank) in pins {
+        let opts = Options {
+            rank_pin: Some(Box::new(move |op: &&str| (*op == "Mul").then_some(pin))),
+            ..Default::default()
+        };
+        let graph = generate_dot_with(&copy_mul(), &opts);
+        let name = format!("rank_pin_{}", rank);
+        let subgraph = find_subgraph(stmts(&graph), &name).expect("no rank pin subgraph");
+
+        assert_eq!(
+            subgraph.stmts[0],
+            Stmt::Attribute(Attribute(
+                Id::Plain(String::from("rank")),
+                Id::Plain(rank.to_string())
+            ))
+        );
+        assert_eq!(node_ids(&subgraph.stmts), ["e_1"]);
+    }
+}
+
+#[test]
+fn operations_share_a_pin_subgraph() {
+    let opts = Options {
+        rank_pin: Some(Box::new(|_| Some(RankPin::Sink))),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert!(dot.contains("subgraph rank_pin_sink {\n    rank=sink\n    e_0\n    e_1\n  }"));
+    assert!(!dot.contains("rank_pin_source"));
+}
+
+fn rank_of(stmts: &[Stmt]) -> &Stmt {
+    stmts
+        .iter()
+        .find(|stmt| matches!(stmt, Stmt::Attribute(Attribute(key, _)) if *key == Id::Plain(String::from("rank"))))
+        .expect("no rank attribute")
+}
+
+#[test]
+fn interfaces_and_their_wires_are_ranked() {
+    let graph = generate_dot_with(&copy_mul(), &Options::default());
+    let stmts = stmts(&graph);
+    let expected = [
+        ("rank_sources", "source", "sources"),
+        ("rank_sources_wires", "same", "n_0"),
+        ("rank_targets", "sink", "targets"),
+        ("rank_targets_wires", "same", "n_5"),
+    ];
+    for (name, rank, node) in expected {
+        let subgraph = find_subgraph(stmts, name).expect("no rank subgraph");
+        assert_eq!(
+            rank_of(&subgraph.stmts),
+            &Stmt::Attribute(Attribute(
+                Id::Plain(String::from("rank")),
+                Id::Plain(rank.to_string())
+            )),
+            "{}",
+            name
+        );
+        assert_eq!(node_ids(&subgraph.stmts), [node], "{}", name);
+    }
+}
+
+#[test]
+fn pass_through_wires_are_not_ranked() {
+    let mut graph = Term::empty();
+    let wire = graph.new_node("A");
+    graph.sources = vec![wire];
+    graph.targets = vec![wire];
+    let graph = generate_dot_with(&graph, &Options::default());
+    let stmts = stmts(&graph);
+
+    assert!(find_subgraph(stmts, "rank_sources").is_some());
+    assert!(find_subgraph(stmts, "rank_targets").is_some());
+    assert!(find_subgraph(stmts, "rank_sources_wires").is_none());
+    assert!(find_subgraph(stmts, "rank_targets_wires").is_none());
+}
+
+#[test]
+fn layers_are_ordered_same_rank_groups() {
+    let mut graph = Term::empty();
+    for label in ["Late", "Early", "Late", "Free"] {
+        graph.new_operation(label, vec!["A"], vec!["A"]);
+    }
+    let opts = Options {
+        layers: Some(Box::new(|op: &&str| match *op {
+            "Early" => Some(0),
+            "Late" => Some(2),
+            _ => None,
+        })),
+        ..Default::default()
+    };
+    let generated = generate_dot_with(&graph, &opts);
+    let stmts = stmts(&generated);
+
+    let early = find_subgraph(stmts, "layer_0").expect("no layer 0");
+    assert_eq!(
+        rank_of(&early.stmts),
+        &Stmt::Attribute(Attribute(
+            Id::Plain(String::from("rank")),
+            Id::Plain(String::from("same"))
+        ))
+    );
+    assert_eq!(node_ids(&early.stmts), ["e_1"]);
+    let late = find_subgraph(stmts, "layer_2").expect("no layer 2");
+    assert_eq!(node_ids(&late.stmts), ["e_0", "e_2"]);
+    assert!(find_subgraph(stmts, "layer_1").is_none());
+
+    // The first operation of each layer is chained to the next layer's
+    let dot = render(&graph, &opts);
+    assert_eq!(
+        lines_with(&dot, "style=invis"),
+        ["e_1 -> e_0 [style=invis]"]
+    );
+}
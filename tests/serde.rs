@@ -0,0 +1,30 @@
+#![cfg(all(feature = "serde", feature = "serde_json"))]
+
+use open_hypergraphs_dot::{solarized_dark, Orientation, Theme};
+
+#[test]
+fn theme_round_trips_through_json() {
+    let theme = Theme {
+        orientation: Orientation::BT,
+        dpi: Some(150),
+        size: Some((8.0, 4.5)),
+        fontname: Some(String::from("Fira Sans")),
+        interface_color: Some(String::from("#93a1a1")),
+        ..solarized_dark()
+    };
+    let json = serde_json::to_string(&theme).unwrap();
+    assert!(json.contains("\"orientation\":\"BT\""));
+
+    let parsed: Theme = serde_json::from_str(&json).unwrap();
+    assert_eq!(format!("{:?}", parsed), format!("{:?}", theme));
+}
+
+#[test]
+fn missing_theme_fields_take_defaults() {
+    let parsed: Theme = serde_json::from_str(r#"{ "bgcolor": "black" }"#).unwrap();
+    let expected = Theme {
+        bgcolor: String::from("black"),
+        ..Theme::default()
+    };
+    assert_eq!(format!("{:?}", parsed), format!("{:?}", expected));
+}
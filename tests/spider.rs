@@ -0,0 +1,57 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::Options;
+
+fn copy_spider() -> Options<&'static str, &'static str> {
+    Options {
+        is_spider: Box::new(|op: &&str| (*op == "Copy").then(|| String::from("green"))),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn spiders_are_filled_circles() {
+    let dot = render(&copy_mul(), &copy_spider());
+
+    assert_eq!(
+        lines_with(&dot, "e_0["),
+        ["e_0[shape=circle style=filled fillcolor=\"green\" label=\"\" width=0.2]"]
+    );
+    // Other operations are still records
+    assert!(lines_with(&dot, "e_1[")[0].contains("shape=record"));
+}
+
+#[test]
+fn spider_wires_attach_to_the_circle() {
+    let dot = render(&copy_mul(), &copy_spider());
+
+    assert_eq!(lines_with(&dot, "-> e_0"), ["n_0 -> e_0"]);
+    assert_eq!(lines_with(&dot, "e_0 ->"), ["e_0 -> n_1", "e_0 -> n_2"]);
+    assert_eq!(
+        lines_with(&dot, "-> e_1"),
+        ["n_3 -> e_1:s_0", "n_4 -> e_1:s_1"]
+    );
+}
+
+#[test]
+fn no_spiders_by_default() {
+    let dot = render(&copy_mul(), &Options::default());
+    assert!(!dot.contains("shape=circle"));
+}
+
+#[test]
+fn copies_are_small_points() {
+    let opts = Options {
+        is_copy: Box::new(|op: &&str| *op == "Copy"),
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+
+    assert_eq!(lines_with(&dot, "e_0["), ["e_0[shape=point width=0.08]"]);
+    assert!(!dot.contains("Copy"));
+    // Every wire meets at the point
+    assert_eq!(lines_with(&dot, "-> e_0"), ["n_0 -> e_0"]);
+    assert_eq!(lines_with(&dot, "e_0 ->"), ["e_0 -> n_1", "e_0 -> n_2"]);
+    assert!(lines_with(&dot, "e_1[")[0].contains("shape=record"));
+}
@@ -0,0 +1,53 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::Options;
+
+/// `Copy ; Mul` where the product is a register
+fn register() -> Term {
+    let mut graph = Term::empty();
+    let (_, (sources, copies)) = graph.new_operation("Copy", vec!["A"], vec!["A", "A"]);
+    let (_, (inputs, targets)) = graph.new_operation("Mul", vec!["A", "A"], vec!["Reg"]);
+    graph.unify(copies[0], inputs[0]);
+    graph.unify(copies[1], inputs[1]);
+    graph.sources = sources;
+    graph.targets = targets;
+    graph
+}
+
+#[test]
+fn state_nodes_take_the_state_shape() {
+    let opts = Options {
+        node_label: Box::new(|ty: &&str| ty.to_string()),
+        node_is_state: Some(Box::new(|ty: &&str| *ty == "Reg")),
+        ..Default::default()
+    };
+    let dot = render(&register(), &opts);
+
+    assert_eq!(
+        lines_with(&dot, "n_5["),
+        ["n_5[shape=square label=\"\" width=0.15 height=0.15 fixedsize=true xlabel=\"Reg\"]"]
+    );
+    assert_eq!(lines_with(&dot, "n_0["), ["n_0[shape=point xlabel=\"A\"]"]);
+    assert_eq!(lines_with(&dot, "shape=square").len(), 1);
+}
+
+#[test]
+fn state_node_shape_is_configurable() {
+    let opts = Options {
+        node_is_state: Some(Box::new(|ty: &&str| *ty == "Reg")),
+        state_node_shape: String::from("Msquare"),
+        ..Default::default()
+    };
+    let dot = render(&register(), &opts);
+    assert_eq!(lines_with(&dot, "shape=Msquare").len(), 1);
+    assert!(lines_with(&dot, "n_5[")[0].starts_with("n_5[shape=Msquare"));
+}
+
+#[test]
+fn nodes_are_points_without_a_state_predicate() {
+    let dot = render(&register(), &Options::default());
+    for line in lines_with(&dot, "xlabel") {
+        assert!(line.contains("shape=point"), "{}", line);
+    }
+}
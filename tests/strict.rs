@@ -0,0 +1,26 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::{generate_dot_strict, render_dot, Options};
+
+#[test]
+fn strict_graphs_are_drawn_without_a_quotient() {
+    let strict = copy_mul().to_strict();
+    let dot = render_dot(&generate_dot_strict(&strict, &Options::default()));
+
+    // Unified nodes are already identified, so there are four wires and no dotted edges
+    assert_eq!(lines_with(&dot, "shape=point").len(), 4);
+    assert!(!dot.contains("dotted"));
+    assert_eq!(
+        lines_with(&dot, "e_1:"),
+        ["n_1 -> e_1:s_0", "n_2 -> e_1:s_1", "e_1:t_0 -> n_3"]
+    );
+    assert_eq!(
+        lines_with(&dot, "sources:"),
+        ["sources:p_0 -> n_0 [style=dashed]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "targets:"),
+        ["n_3 -> targets:p_0 [style=dashed]"]
+    );
+}
@@ -0,0 +1,29 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::{graph_summary, GraphSummary};
+
+#[test]
+fn summary_counts_the_graph() {
+    assert_eq!(
+        graph_summary(&copy_mul()),
+        GraphSummary {
+            nodes: 6,
+            edges: 2,
+            sources: 1,
+            targets: 1,
+            quotient_pairs: 2,
+        }
+    );
+    assert_eq!(
+        graph_summary(&operation("Big", 4, 3)),
+        GraphSummary {
+            nodes: 7,
+            edges: 1,
+            sources: 4,
+            targets: 3,
+            quotient_pairs: 0,
+        }
+    );
+    assert_eq!(graph_summary(&Term::empty()).nodes, 0);
+}
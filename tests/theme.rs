@@ -0,0 +1,188 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::{
+    solarized_dark, solarized_light, Options, Orientation, Theme, ThemeError, ThemeOverrides,
+};
+
+fn base() -> Theme {
+    Theme {
+        dpi: Some(100),
+        fontname: Some(String::from("Fira Sans")),
+        ..solarized_light()
+    }
+}
+
+#[test]
+fn overrides_replace_only_the_fields_they_set() {
+    let theme = Theme::with_overrides(
+        &base(),
+        ThemeOverrides {
+            bgcolor: Some(String::from("white")),
+            dpi: Some(300),
+            orientation: Some(Orientation::LR),
+            penwidth: Some(2.0),
+            ..Default::default()
+        },
+    );
+    let expected = Theme {
+        bgcolor: String::from("white"),
+        dpi: Some(300),
+        orientation: Orientation::LR,
+        penwidth: Some(2.0),
+        ..base()
+    };
+    assert_eq!(format!("{:?}", theme), format!("{:?}", expected));
+}
+
+#[test]
+fn empty_overrides_keep_the_base() {
+    let theme = Theme::with_overrides(&base(), ThemeOverrides::default());
+    assert_eq!(format!("{:?}", theme), format!("{:?}", base()));
+}
+
+#[test]
+fn later_overrides_take_precedence() {
+    let organization = ThemeOverrides {
+        accent: Some(String::from("#ff8800")),
+        fontname: Some(String::from("Inter")),
+        ..Default::default()
+    };
+    let diagram = ThemeOverrides {
+        fontname: Some(String::from("Fira Mono")),
+        ..Default::default()
+    };
+    let theme = Theme::with_overrides(&Theme::with_overrides(&base(), organization), diagram);
+
+    assert_eq!(theme.accent, "#ff8800");
+    assert_eq!(theme.fontname.as_deref(), Some("Fira Mono"));
+    assert_eq!(theme.dpi, Some(100));
+    assert_eq!(theme.bgcolor, solarized_light().bgcolor);
+}
+
+#[test]
+fn try_new_accepts_graphviz_colors() {
+    let theme = Theme::try_new("white", "Red3", "#abc", "#11223344", Orientation::LR).unwrap();
+    assert_eq!(theme.fontcolor, "Red3");
+    // Short hex strings are expanded
+    assert_eq!(theme.color, "#aabbcc");
+    assert!(Theme::try_new("gray0", "grey100", "transparent", "none", Orientation::LR).is_ok());
+}
+
+#[test]
+fn invalid_colors_are_reported() {
+    let invalid = [
+        ("bgcolor", ["invis", "black", "black", "black"]),
+        ("fontcolor", ["black", "black1", "black", "black"]),
+        ("color", ["black", "black", "white3", "black"]),
+        ("accent", ["black", "black", "black", "#12345"]),
+        ("bgcolor", ["red5", "black", "black", "black"]),
+        ("bgcolor", ["gray101", "black", "black", "black"]),
+        ("bgcolor", ["#ggg", "black", "black", "black"]),
+    ];
+    for (field, [bgcolor, fontcolor, color, accent]) in invalid {
+        let error = Theme::try_new(bgcolor, fontcolor, color, accent, Orientation::TB).unwrap_err();
+        let value = [bgcolor, fontcolor, color, accent]
+            .into_iter()
+            .find(|value| *value != "black")
+            .unwrap();
+        assert_eq!(
+            error,
+            ThemeError::InvalidColor {
+                field,
+                value: value.to_string()
+            }
+        );
+    }
+
+    let error = Theme::try_new("white", "black", "invis", "red", Orientation::TB).unwrap_err();
+    assert_eq!(error.to_string(), "invalid color for color: \"invis\"");
+}
+
+#[test]
+fn validate_checks_every_color() {
+    assert!(base().validate().is_ok());
+
+    let theme = Theme {
+        interface_color: Some(String::from("white4")),
+        ..base()
+    };
+    assert_eq!(
+        theme.validate().unwrap_err(),
+        ThemeError::InvalidColor {
+            field: "interface_color",
+            value: String::from("white4")
+        }
+    );
+}
+
+#[test]
+fn fonts_are_set_on_node_and_edge_defaults() {
+    let opts = Options {
+        theme: Theme {
+            fontname: Some(String::from("Fira Sans")),
+            fontsize: Some(10.5),
+            ..Theme::default()
+        },
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert_eq!(
+        lines_with(&dot, "fontname"),
+        [
+            "node[shape=record style=rounded fontcolor=\"white\" color=\"white\" fontname=\"Fira Sans\" fontsize=10.5]",
+            "edge[fontcolor=\"white\" color=\"white\" arrowhead=none fontname=\"Fira Sans\" fontsize=10.5]"
+        ]
+    );
+
+    let dot = render(&copy_mul(), &Options::default());
+    assert!(!dot.contains("fontname") && !dot.contains("fontsize"));
+}
+
+#[test]
+fn solarized_presets_use_the_canonical_colors() {
+    let light = solarized_light();
+    assert_eq!(
+        [&light.bgcolor, &light.fontcolor, &light.color],
+        ["#fdf6e3", "#586e75", "#657b83"]
+    );
+    let dark = solarized_dark();
+    assert_eq!(
+        [&dark.bgcolor, &dark.fontcolor, &dark.color],
+        ["#002b36", "#93a1a1", "#839496"]
+    );
+
+    for theme in [light, dark] {
+        assert!(theme.validate().is_ok());
+        let opts = Options {
+            theme: theme.clone(),
+            ..Default::default()
+        };
+        let dot = render(&copy_mul(), &opts);
+        assert_eq!(
+            lines_with(&dot, "bgcolor"),
+            [format!("bgcolor=\"{}\"", theme.bgcolor)]
+        );
+        assert!(lines_with(&dot, "node[")[0].contains(&format!("color=\"{}\"", theme.color)));
+    }
+}
+
+#[test]
+fn penwidth_is_set_on_node_and_edge_defaults() {
+    let opts = Options {
+        theme: Theme {
+            penwidth: Some(2.5),
+            ..Theme::default()
+        },
+        ..Default::default()
+    };
+    let dot = render(&copy_mul(), &opts);
+    assert_eq!(
+        lines_with(&dot, "penwidth"),
+        [
+            "node[shape=record style=rounded fontcolor=\"white\" color=\"white\" penwidth=2.5]",
+            "edge[fontcolor=\"white\" color=\"white\" arrowhead=none penwidth=2.5]"
+        ]
+    );
+    assert!(!render(&copy_mul(), &Options::default()).contains("penwidth"));
+}
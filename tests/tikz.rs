@@ -0,0 +1,28 @@
+mod common;
+
+use common::*;
+use open_hypergraphs::lax::{Hyperedge, NodeId};
+use open_hypergraphs_dot::{generate_tikz, Options, TikzOptions};
+
+#[test]
+fn dangling_nodes_are_drawn_as_coordinates() {
+    let mut graph = Term::empty();
+    let x = graph.new_node("A");
+    graph.new_edge(
+        "F",
+        Hyperedge {
+            sources: vec![x],
+            targets: vec![NodeId(9)],
+        },
+    );
+    graph.sources = vec![x];
+    graph.targets = vec![NodeId(7)];
+
+    let tikz = generate_tikz(&graph, &Options::default(), &TikzOptions::default());
+    assert!(tikz.contains("\\coordinate (n9) at (4.50, 0.00);"));
+    assert!(tikz.contains("\\coordinate (n7) at (1.50, -1.00);"));
+    assert!(tikz.contains("\\draw (e0.east) -- (n9);"));
+    assert!(tikz.contains("\\draw[dashed] (n7) -- (t0);"));
+    // Only referenced indices are drawn
+    assert!(!tikz.contains("(n8)"));
+}
@@ -0,0 +1,39 @@
+mod common;
+use common::*;
+
+use open_hypergraphs_dot::{generate_dot_transposed, render_dot, Options};
+
+#[test]
+fn transposed_graphs_swap_the_interfaces() {
+    let dot = render_dot(&generate_dot_transposed(&copy_mul(), &Options::default()));
+
+    assert_eq!(
+        lines_with(&dot, "sources:"),
+        ["sources:p_0 -> n_5 [style=dashed]"]
+    );
+    assert_eq!(
+        lines_with(&dot, "targets:"),
+        ["n_0 -> targets:p_0 [style=dashed]"]
+    );
+}
+
+#[test]
+fn transposed_graphs_reverse_the_connections() {
+    let opts = Options::default();
+    let dot = render_dot(&generate_dot_transposed(&copy_mul(), &opts));
+
+    assert_eq!(
+        lines_with(&dot, "e_0:"),
+        ["e_0:s_0 -> n_0", "n_1 -> e_0:t_0", "n_2 -> e_0:t_1"]
+    );
+    assert_eq!(
+        lines_with(&dot, "e_1:"),
+        ["e_1:s_0 -> n_3", "e_1:s_1 -> n_4", "n_5 -> e_1:t_0"]
+    );
+
+    // Operation records and quotient edges are unchanged
+    let original = render(&copy_mul(), &opts);
+    for needle in ["e_0[", "e_1[", "dotted"] {
+        assert_eq!(lines_with(&dot, needle), lines_with(&original, needle));
+    }
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use common::*;
+use open_hypergraphs_dot::{generate_dot_with, render_dot, write_dot, Options};
+
+#[test]
+fn write_dot_matches_render_dot() {
+    let opts = Options {
+        frame: Some(String::from("Frame")),
+        legend: Some(vec![(String::from("Copy"), String::from("red"))]),
+        minimap: true,
+        ..Default::default()
+    };
+    for graph in [
+        generate_dot_with(&copy_mul(), &opts),
+        generate_dot_with(&Term::empty(), &Options::default()),
+    ] {
+        let mut written = Vec::new();
+        write_dot(&graph, &mut written).expect("writing to a Vec can't fail");
+        assert_eq!(String::from_utf8(written).unwrap(), render_dot(&graph));
+    }
+}
+
+#[test]
+fn write_dot_matches_render_dot_for_undirected_graphs() {
+    let opts = Options {
+        directed: false,
+        strict: true,
+        ..Default::default()
+    };
+    let graph = generate_dot_with(&copy_mul(), &opts);
+    let mut written = Vec::new();
+    write_dot(&graph, &mut written).unwrap();
+    assert_eq!(String::from_utf8(written).unwrap(), render_dot(&graph));
+}